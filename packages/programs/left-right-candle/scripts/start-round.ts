@@ -16,9 +16,32 @@ const PROGRAM_ID = new PublicKey("81K7nKnv7JiRhBCRNmagKot27Yu82eRWeeNA7dtGGaX6")
 // Anchor discriminator for start_round (sha256("global:start_round")[0:8])
 const START_ROUND_DISCRIMINATOR = Buffer.from([144, 144, 43, 7, 193, 42, 217, 215]);
 
+// Pyth receiver program (owner of price update accounts) and the push
+// oracle whose sponsored price feed accounts are updated continuously
+const PYTH_RECEIVER_PROGRAM_ID = new PublicKey("rec5EKMGg6MxZYaMdyBfgwp4d5rB9T1VQH5pJv5LtFJ");
+const PYTH_PUSH_ORACLE_ID = new PublicKey("pythWSnswVUd12oZpeFP8e9CVaEqJg25g1Vtc2biRsT");
+const INSTRUCTIONS_SYSVAR_ID = new PublicKey("Sysvar1nstructions1111111111111111111111111");
+
+const PYTH_FEED_IDS: Record<string, string> = {
+  WIF: "4ca4beeca86f0d164160323817a4e42b10010a724c2217c6ee41b54cd4cc61fc",
+  BONK: "72b021217ca3fe68922a19aaf990109cb9d84e9ad004b4d2025ad6f529314419",
+  SOL: "ef0d8b6fda2ceba41da15d4095d1da392a0d2f8ed0c6c7bc0f4cfac8c280b56d",
+  BTC: "e62df6c8b4a85fe1a67db44dc12de5db330f7ac66b72dc658afedf0f4a415b43",
+};
+
+// PriceSource::Pyth
+const PRICE_SOURCE_PYTH = 0;
+
 async function main() {
   const asset = process.argv[2] || "WIF";
-  const priceArg = process.argv[3];
+  const bettingHours = parseFloat(process.argv[3] || "12");
+  const waitingHours = parseFloat(process.argv[4] || "12");
+
+  const feedId = PYTH_FEED_IDS[asset];
+  if (!feedId) {
+    console.error(`No Pyth feed for ${asset}`);
+    return;
+  }
 
   // Load wallet
   const walletPath = path.join(os.homedir(), ".config/solana/id.json");
@@ -31,28 +54,19 @@ async function main() {
   // Connect to devnet
   const connection = new Connection("https://api.devnet.solana.com", "confirmed");
 
-  // Get current price from CoinGecko if not provided
-  let startPrice: bigint;
-  if (priceArg) {
-    startPrice = BigInt(Math.round(parseFloat(priceArg) * 1e8));
-  } else {
-    const coinIds: Record<string, string> = {
-      WIF: "dogwifcoin",
-      BONK: "bonk",
-      SOL: "solana",
-      BTC: "bitcoin",
-    };
-    const coinId = coinIds[asset] || "dogwifcoin";
-
-    console.log(`Fetching ${asset} price from CoinGecko...`);
-    const res = await fetch(
-      `https://api.coingecko.com/api/v3/simple/price?ids=${coinId}&vs_currencies=usd`
-    );
-    const data = await res.json() as Record<string, { usd?: number }>;
-    const price = data[coinId]?.usd || 1.0;
-    startPrice = BigInt(Math.round(price * 1e8));
-    console.log(`Current price: $${price} (${startPrice} scaled)`);
+  // Sponsored price feed account of the asset (shard 0); the program reads
+  // the start price from it
+  const shard = Buffer.alloc(2);
+  const [priceFeed] = PublicKey.findProgramAddressSync(
+    [shard, Buffer.from(feedId, "hex")],
+    PYTH_PUSH_ORACLE_ID
+  );
+  const feedAccount = await connection.getAccountInfo(priceFeed);
+  if (!feedAccount || !feedAccount.owner.equals(PYTH_RECEIVER_PROGRAM_ID)) {
+    console.error("Price feed account not found:", priceFeed.toBase58());
+    return;
   }
+  console.log("Price feed:", priceFeed.toBase58());
 
   // Derive config PDA
   const [configPda] = PublicKey.findProgramAddressSync(
@@ -91,9 +105,11 @@ async function main() {
   console.log("Vault PDA:", vaultPda.toBase58());
 
   // Serialize instruction data
-  // Discriminator (8) + asset_symbol as String (4 + len) + start_price (i64)
+  // Discriminator (8) + asset_symbol (4 + len) + price_sources (4 + 1)
+  // + oracle_quorum (1) + attested_price (None: 1) + twap_samples (1)
+  // + betting_duration_secs (8) + waiting_duration_secs (8)
   const assetBuffer = Buffer.from(asset, "utf-8");
-  const data = Buffer.alloc(8 + 4 + assetBuffer.length + 8);
+  const data = Buffer.alloc(8 + 4 + assetBuffer.length + 5 + 1 + 1 + 1 + 8 + 8);
   let offset = 0;
 
   // Discriminator
@@ -106,15 +122,44 @@ async function main() {
   assetBuffer.copy(data, offset);
   offset += assetBuffer.length;
 
-  // start_price (i64 LE)
-  data.writeBigInt64LE(startPrice, offset);
+  // price_sources: [Pyth]
+  data.writeUInt32LE(1, offset);
+  offset += 4;
+  data.writeUInt8(PRICE_SOURCE_PYTH, offset);
+  offset += 1;
+
+  // oracle_quorum
+  data.writeUInt8(1, offset);
+  offset += 1;
+
+  // attested_price: None
+  data.writeUInt8(0, offset);
+  offset += 1;
+
+  // twap_samples: 0 (spot price)
+  data.writeUInt8(0, offset);
+  offset += 1;
+
+  // betting_duration_secs and waiting_duration_secs (i64 LE)
+  data.writeBigInt64LE(BigInt(Math.round(bettingHours * 3600)), offset);
+  offset += 8;
+  data.writeBigInt64LE(BigInt(Math.round(waitingHours * 3600)), offset);
 
+  // Optional accounts left out are passed as the program id
+  const none = { pubkey: PROGRAM_ID, isSigner: false, isWritable: false };
   const instruction = new TransactionInstruction({
     keys: [
       { pubkey: configPda, isSigner: false, isWritable: true },
       { pubkey: roundPda, isSigner: false, isWritable: true },
-      { pubkey: vaultPda, isSigner: false, isWritable: false },
+      { pubkey: vaultPda, isSigner: false, isWritable: true },
+      none, // stake_mint (SOL round)
+      none, // mint_config
+      none, // vault_token
+      { pubkey: priceFeed, isSigner: false, isWritable: false },
+      none, // switchboard_feed
+      { pubkey: INSTRUCTIONS_SYSVAR_ID, isSigner: false, isWritable: false },
       { pubkey: wallet.publicKey, isSigner: true, isWritable: true },
+      none, // token_program
       { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
     ],
     programId: PROGRAM_ID,
@@ -130,8 +175,7 @@ async function main() {
   console.log("\nRound details:");
   console.log(`  Round ID: ${roundCounter}`);
   console.log(`  Asset: ${asset}`);
-  console.log(`  Start Price: $${Number(startPrice) / 1e8}`);
-  console.log(`  Duration: 24 hours (12h betting + 12h waiting)`);
+  console.log(`  Duration: ${bettingHours}h betting + ${waitingHours}h waiting`);
 }
 
 main().catch(console.error);
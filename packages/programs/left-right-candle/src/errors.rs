@@ -46,4 +46,175 @@ pub enum LeftRightError {
 
     #[msg("Round is not in settling state")]
     RoundNotSettling,
+
+    #[msg("Price feed account is not a valid Pyth price update")]
+    InvalidPriceFeed,
+
+    #[msg("Oracle price is too old")]
+    StalePrice,
+
+    #[msg("Oracle confidence interval is too wide")]
+    PriceConfidenceTooWide,
+
+    #[msg("Price feed does not match the round's feed")]
+    PriceFeedMismatch,
+
+    #[msg("Invalid oracle configuration")]
+    InvalidOracleConfig,
+
+    #[msg("Invalid price signer set or threshold")]
+    InvalidSignerSet,
+
+    #[msg("Malformed Ed25519 price attestation")]
+    InvalidAttestation,
+
+    #[msg("Not enough price signers attested to this price")]
+    InsufficientAttestations,
+
+    #[msg("Price input does not match the round's price source")]
+    InvalidPriceSource,
+
+    #[msg("Invalid dispute window or bond")]
+    InvalidDisputeConfig,
+
+    #[msg("Round has no pending settlement proposal")]
+    RoundNotProposed,
+
+    #[msg("Round settlement is not disputed")]
+    RoundNotDisputed,
+
+    #[msg("Challenge window has closed")]
+    ChallengeWindowClosed,
+
+    #[msg("Challenge window is still open")]
+    ChallengeWindowOpen,
+
+    #[msg("Invalid TWAP configuration")]
+    InvalidTwapConfig,
+
+    #[msg("Price sampling is not open for this round")]
+    SamplingNotOpen,
+
+    #[msg("Price sample is too close to the previous one")]
+    SampleTooSoon,
+
+    #[msg("Not enough price samples to settle")]
+    NotEnoughSamples,
+
+    #[msg("Invalid price source set or quorum")]
+    InvalidPriceSources,
+
+    #[msg("Not enough fresh price sources to reach quorum")]
+    OracleQuorumNotMet,
+
+    #[msg("Treasury must sign to refund fees")]
+    TreasurySignatureRequired,
+
+    #[msg("Round can only be cancelled before settlement")]
+    RoundNotCancellable,
+
+    #[msg("Round is not cancelled")]
+    RoundNotCancelled,
+
+    #[msg("Invalid void grace period")]
+    InvalidVoidGracePeriod,
+
+    #[msg("Round can't be voided until its grace period has passed")]
+    VoidGracePeriodActive,
+
+    #[msg("Round duration is outside the configured bounds")]
+    InvalidRoundDuration,
+
+    #[msg("Round betting period has not ended yet")]
+    BettingPeriodActive,
+
+    #[msg("Payout has not been processed for this bet")]
+    PayoutNotProcessed,
+
+    #[msg("Round still has payouts or refunds outstanding")]
+    RoundNotFinished,
+
+    #[msg("Round still has open bet accounts")]
+    BetsNotClosed,
+
+    #[msg("Round vault must be swept first")]
+    VaultNotEmpty,
+
+    #[msg("Cannot bet on the opposite side of an existing position")]
+    PositionSideMismatch,
+
+    #[msg("Bet has been withdrawn")]
+    BetWithdrawn,
+
+    #[msg("Invalid weight curve")]
+    InvalidWeightCurve,
+
+    #[msg("Implied payout multiplier is below the requested minimum")]
+    MultiplierBelowMinimum,
+
+    #[msg("Bet exceeds the per-wallet limit for this round")]
+    RoundExposureExceeded,
+
+    #[msg("Bet exceeds the per-wallet 24 hour limit")]
+    DailyExposureExceeded,
+
+    #[msg("Bet is too large for the current pool size")]
+    BetExceedsPoolLimit,
+
+    #[msg("Stake mint is not enabled for token rounds")]
+    InvalidStakeMint,
+
+    #[msg("Token round requires token accounts")]
+    TokenAccountRequired,
+
+    #[msg("Token account does not match the stake mint or owner")]
+    InvalidTokenAccount,
+
+    #[msg("Rolled-over pool must go to a SOL round first")]
+    RolloverPending,
+
+    #[msg("Stake mint has a token extension that is not supported")]
+    UnsupportedMintExtension,
+
+    #[msg("Batch accounts must be (bet, bettor) groups of this round")]
+    InvalidBatchAccounts,
+
+    #[msg("Invalid payout root")]
+    InvalidPayoutRoot,
+
+    #[msg("Payout root total exceeds the vault balance")]
+    PayoutRootExceedsVault,
+
+    #[msg("Round payouts are not posted as a merkle root")]
+    RoundNotClaiming,
+
+    #[msg("Invalid merkle proof")]
+    InvalidMerkleProof,
+
+    #[msg("Sampling window has closed")]
+    SamplingClosed,
+
+    #[msg("Claim deadline has passed")]
+    ClaimDeadlinePassed,
+
+    #[msg("Claim deadline has not passed yet")]
+    ClaimDeadlineNotPassed,
+
+    #[msg("Volume stats account for the round's stake is missing")]
+    MissingUserStats,
+
+    #[msg("Bet is still owed its treasury fee refund")]
+    FeeRefundOwed,
+
+    #[msg("Bet is not owed a treasury fee refund")]
+    NoFeeRefundOwed,
+
+    #[msg("Price publish time is in the future")]
+    PriceFromFuture,
+
+    #[msg("Exit penalty basis points must be <= 10000")]
+    InvalidExitPenaltyBps,
+
+    #[msg("Dynamic max bet basis points must be <= 10000")]
+    InvalidDynamicMaxBetBps,
 }
@@ -1,11 +1,31 @@
 use anchor_lang::prelude::*;
 
+use crate::oracle::PriceReadings;
+use crate::state::{Price, PriceSource, TiePolicy, WeightCurve};
+
 #[event]
 pub struct RoundStarted {
     pub round_id: u64,
     pub asset_symbol: String,
-    pub start_price: i64,
+    pub price_sources: Vec<PriceSource>,
+    pub oracle_quorum: u8,
+    pub price_feed: Pubkey,
+    /// Pyth feed id of `price_feed`
+    pub price_feed_id: [u8; 32],
+    pub switchboard_feed: Pubkey,
+    /// Number of samples averaged for the end price (0 = spot price)
+    pub twap_samples: u8,
+    pub tie_policy: TiePolicy,
+    pub weight_curve: WeightCurve,
+    /// SPL mint bets are staked in (`None` = SOL)
+    pub stake_mint: Option<Pubkey>,
+    /// Lamports rolled over from tied rounds, paid to this round's winners
+    pub bonus_pool: u64,
+    /// Median of `start_readings`
+    pub start_price: Price,
+    pub start_readings: PriceReadings,
     pub start_time: i64,
+    pub betting_end_time: i64,
     pub end_time: i64,
 }
 
@@ -23,14 +43,85 @@ pub struct BetPlaced {
     /// 1% fee sent to referrer (if any)
     pub referrer_fee: u64,
     pub bet_index: u32,
+    /// Timing weight of this bet (scaled by 100, e.g., 150 = 1.5x)
+    pub weight: u64,
+    /// Bettor's total pool contribution in the round after this bet
+    pub position_amount: u64,
     pub referrer: Option<Pubkey>,
 }
 
+/// Betting closed; pools are final from here on
+#[event]
+pub struct BetWithdrawn {
+    pub round_id: u64,
+    pub bettor: Pubkey,
+    pub side: u8,
+    /// Pool contribution returned, after the penalty
+    pub amount: u64,
+    pub penalty: u64,
+    /// Penalty sent to treasury (true) or added to the round's bonus pool
+    pub penalty_to_treasury: bool,
+}
+
+#[event]
+pub struct WeightCurveUpdated {
+    pub weight_curve: WeightCurve,
+}
+
+#[event]
+pub struct ExposureLimitsUpdated {
+    pub max_round_exposure_lamports: u64,
+    pub max_daily_exposure_lamports: u64,
+}
+
+#[event]
+pub struct DynamicMaxBetUpdated {
+    pub dynamic_max_bet_bps: u16,
+    pub dynamic_max_bet_floor_lamports: u64,
+}
+
+#[event]
+pub struct MintConfigUpdated {
+    pub mint: Pubkey,
+    pub min_bet: u64,
+    pub max_bet: u64,
+    pub max_round_exposure: u64,
+    pub max_daily_exposure: u64,
+    pub dynamic_max_bet_floor: u64,
+}
+
+#[event]
+pub struct ExitPenaltyUpdated {
+    pub exit_penalty_bps: u16,
+    pub exit_penalty_to_treasury: bool,
+}
+
+#[event]
+pub struct RoundLocked {
+    pub round_id: u64,
+    pub locked_at: i64,
+    pub left_pool: u64,
+    pub right_pool: u64,
+    pub left_weighted_pool: u64,
+    pub right_weighted_pool: u64,
+    pub bonus_pool: u64,
+    pub bet_count: u32,
+    /// Implied payout per lamport on LEFT, in bps (25_000 = 2.5x, 0 = no bets)
+    pub left_odds_bps: u64,
+    /// Implied payout per lamport on RIGHT, in bps (25_000 = 2.5x, 0 = no bets)
+    pub right_odds_bps: u64,
+}
+
 #[event]
 pub struct RoundSettled {
     pub round_id: u64,
-    pub start_price: i64,
-    pub end_price: i64,
+    pub settler: Pubkey,
+    pub price_sources: Vec<PriceSource>,
+    pub start_price: Price,
+    pub end_price: Price,
+    /// Individual source readings behind the proposed end price
+    pub end_readings: PriceReadings,
+    /// LEFT=0, RIGHT=1, tie refunded or rolled over=2
     pub winning_side: u8,
     /// Total pool (already fee-adjusted from bet time)
     pub total_pool: u64,
@@ -44,6 +135,19 @@ pub struct PayoutProcessed {
     pub amount: u64,
 }
 
+#[event]
+pub struct PayoutBatchProcessed {
+    pub round_id: u64,
+    /// Bets paid (or refunded) by this batch
+    pub processed: u32,
+    /// Bets in the batch that were already paid or closed
+    pub skipped: u32,
+    /// Total paid out of the vault by this batch
+    pub amount: u64,
+    /// Bets in the round still waiting for a payout
+    pub payouts_remaining: u32,
+}
+
 #[event]
 pub struct ConfigUpdated {
     pub fee_bps: u16,
@@ -58,3 +162,192 @@ pub struct ReferrerPaid {
     pub referrer: Pubkey,
     pub amount: u64,
 }
+
+#[event]
+pub struct OracleConfigUpdated {
+    pub max_price_age_secs: i64,
+    pub max_conf_bps: u16,
+}
+
+#[event]
+pub struct PriceSignersUpdated {
+    pub price_signers: Vec<Pubkey>,
+    pub signer_threshold: u8,
+}
+
+#[event]
+pub struct DisputeConfigUpdated {
+    pub dispute_window_secs: i64,
+    pub settlement_bond_lamports: u64,
+}
+
+#[event]
+pub struct SettlementProposed {
+    pub round_id: u64,
+    pub proposer: Pubkey,
+    pub end_price: Price,
+    pub winning_side: u8,
+    pub bond: u64,
+    pub challenge_end_time: i64,
+}
+
+#[event]
+pub struct SettlementDisputed {
+    pub round_id: u64,
+    pub disputer: Pubkey,
+    pub bond: u64,
+}
+
+#[event]
+pub struct DisputeResolved {
+    pub round_id: u64,
+    pub resolver: Pubkey,
+    pub end_price: Price,
+    pub winning_side: u8,
+    /// Proposer or disputer, whichever was right; receives both bonds
+    pub bond_winner: Pubkey,
+    /// Bond slashed from the losing party
+    pub slashed_bond: u64,
+}
+
+#[event]
+pub struct TwapConfigUpdated {
+    pub twap_min_samples: u8,
+    pub twap_min_spacing_secs: i64,
+}
+
+#[event]
+pub struct PriceSampleRecorded {
+    pub round_id: u64,
+    pub price: Price,
+    pub sample_count: u16,
+}
+
+#[event]
+pub struct TiePolicyUpdated {
+    pub tie_policy: TiePolicy,
+}
+
+#[event]
+pub struct RefundFeesUpdated {
+    pub refund_fees: bool,
+}
+
+/// One side of the round had no bets: no price is read, every bet is refunded
+#[event]
+pub struct RoundNoContest {
+    pub round_id: u64,
+    pub left_pool: u64,
+    pub right_pool: u64,
+    pub bet_count: u32,
+}
+
+#[event]
+pub struct RoundCancelled {
+    pub round_id: u64,
+    /// Pool to be refunded with `refund_bet`
+    pub total_pool: u64,
+    pub bet_count: u32,
+}
+
+#[event]
+pub struct VoidGracePeriodUpdated {
+    pub void_grace_secs: i64,
+}
+
+/// Round went unsettled past its grace period, every bet is refunded
+#[event]
+pub struct RoundVoided {
+    pub round_id: u64,
+    pub end_time: i64,
+    pub voided_at: i64,
+    pub total_pool: u64,
+    pub bet_count: u32,
+}
+
+#[event]
+pub struct DurationBoundsUpdated {
+    pub min_betting_secs: i64,
+    pub max_betting_secs: i64,
+    pub min_waiting_secs: i64,
+    pub max_waiting_secs: i64,
+}
+
+#[event]
+pub struct BetRefunded {
+    pub round_id: u64,
+    pub bettor: Pubkey,
+    /// Pool contribution returned from the vault
+    pub amount: u64,
+    /// Fees returned from the treasury (if fee refunds are enabled)
+    pub fee_refund: u64,
+    /// Fees due back but left unpaid because the treasury did not co-sign,
+    /// recorded on the bet until settled
+    pub fee_owed: u64,
+}
+
+#[event]
+pub struct FeeRefundSettled {
+    pub round_id: u64,
+    pub bettor: Pubkey,
+    /// Owed treasury fee paid back to the bettor
+    pub amount: u64,
+}
+
+#[event]
+pub struct PoolRolledOver {
+    pub round_id: u64,
+    /// Round whose bonus pool receives the funds
+    pub next_round_id: u64,
+    pub amount: u64,
+}
+
+#[event]
+pub struct BetClosed {
+    pub round_id: u64,
+    pub bettor: Pubkey,
+    pub bet_index: u32,
+}
+
+#[event]
+pub struct VaultSwept {
+    pub round_id: u64,
+    /// Residual stake (lamports, or stake mint units) sent to the treasury
+    pub amount: u64,
+}
+
+#[event]
+pub struct RoundClosed {
+    pub round_id: u64,
+}
+
+#[event]
+pub struct PriceSamplesClosed {
+    pub round_id: u64,
+    pub payer: Pubkey,
+}
+
+#[event]
+pub struct PayoutRootPosted {
+    pub round_id: u64,
+    pub merkle_root: [u8; 32],
+    pub total_payout: u64,
+    pub leaf_count: u32,
+    pub claim_deadline: i64,
+}
+
+#[event]
+pub struct PayoutClaimed {
+    pub round_id: u64,
+    pub bettor: Pubkey,
+    /// Leaf index in the payout tree
+    pub index: u32,
+    pub amount: u64,
+}
+
+#[event]
+pub struct PayoutRootClosed {
+    pub round_id: u64,
+    /// Payouts left unclaimed at the deadline, swept to the treasury
+    pub unclaimed_amount: u64,
+}
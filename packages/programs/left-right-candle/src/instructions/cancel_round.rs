@@ -0,0 +1,82 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::LeftRightError;
+use crate::events::RoundCancelled;
+use crate::instructions::settle_round::roll_over_pool;
+use crate::state::{Config, Round, RoundStatus};
+
+#[derive(Accounts)]
+pub struct CancelRound<'info> {
+    #[account(
+        mut,
+        seeds = [Config::SEED],
+        bump = config.bump,
+        has_one = admin @ LeftRightError::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [Round::SEED, round.round_id.to_le_bytes().as_ref()],
+        bump = round.bump,
+    )]
+    pub round: Account<'info, Round>,
+
+    /// Round vault PDA holding the pool
+    /// CHECK: PDA owned by program
+    #[account(
+        mut,
+        seeds = [b"vault", round.round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    /// Vault of the next round to start, receives the round's bonus pool
+    /// CHECK: PDA owned by program
+    #[account(
+        mut,
+        seeds = [b"vault", config.round_counter.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub next_vault: SystemAccount<'info>,
+
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Abort a round before settlement (e.g. wrong symbol or bad start price)
+///
+/// Bets are refunded with `refund_bet` (or `process_payout`), like a
+/// no-contest round. A bonus pool rolled over
+/// from earlier ties moves on to the next round.
+pub fn handler(ctx: Context<CancelRound>) -> Result<()> {
+    let round = &mut ctx.accounts.round;
+
+    require!(
+        round.status == RoundStatus::Open || round.status == RoundStatus::Locked,
+        LeftRightError::RoundNotCancellable
+    );
+
+    let bonus_pool = round.bonus_pool;
+    roll_over_pool(
+        round,
+        &mut ctx.accounts.config,
+        bonus_pool,
+        ctx.accounts.vault.to_account_info(),
+        ctx.bumps.vault,
+        ctx.accounts.next_vault.to_account_info(),
+        ctx.accounts.system_program.to_account_info(),
+    )?;
+
+    round.winning_side = None;
+    round.status = RoundStatus::Cancelled;
+
+    emit!(RoundCancelled {
+        round_id: round.round_id,
+        total_pool: round.total_pool(),
+        bet_count: round.bet_count,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,111 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::errors::LeftRightError;
+use crate::instructions::process_payout::pay_bet;
+use crate::stake::Stake;
+use crate::state::{Bet, Config, Round};
+
+#[derive(Accounts)]
+pub struct Claim<'info> {
+    #[account(
+        seeds = [Config::SEED],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [Round::SEED, round.round_id.to_le_bytes().as_ref()],
+        bump = round.bump,
+    )]
+    pub round: Account<'info, Round>,
+
+    #[account(
+        mut,
+        seeds = [
+            Bet::SEED,
+            round.round_id.to_le_bytes().as_ref(),
+            bettor.key().as_ref()
+        ],
+        bump = bet.bump,
+        constraint = bet.round_id == round.round_id,
+    )]
+    pub bet: Account<'info, Bet>,
+
+    /// Round vault holding pool funds (already fee-adjusted)
+    /// CHECK: PDA owned by program
+    #[account(
+        mut,
+        seeds = [b"vault", round.round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    /// Bettor claiming their own payout
+    #[account(mut)]
+    pub bettor: Signer<'info>,
+
+    /// Treasury returning the platform fee on refunds (only when fee
+    /// refunds are enabled)
+    #[account(
+        mut,
+        constraint = treasury.key() == config.treasury @ LeftRightError::Unauthorized
+    )]
+    pub treasury: Option<Signer<'info>>,
+
+    /// Stake mint of a token round
+    #[account(
+        constraint = round.stake_mint == Some(stake_mint.key()) @ LeftRightError::InvalidStakeMint
+    )]
+    pub stake_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    /// Token account of a token round the vault PDA holds bets in
+    #[account(
+        mut,
+        seeds = [Round::TOKEN_VAULT_SEED, round.round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub vault_token: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Bettor's token account receiving the payout (token rounds)
+    #[account(mut)]
+    pub bettor_token: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Treasury's token account returning the fee (token rounds)
+    #[account(mut)]
+    pub treasury_token: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Claim a bet's payout or refund, signed by the bettor
+///
+/// Pays exactly what `process_payout` would, so winners don't have to wait
+/// for the keeper loop; whichever runs first pays the bet.
+pub fn handler(ctx: Context<Claim>) -> Result<()> {
+    let stake = Stake::new(
+        &ctx.accounts.round,
+        &ctx.accounts.vault,
+        ctx.bumps.vault,
+        &ctx.accounts.stake_mint,
+        &ctx.accounts.vault_token,
+        &ctx.accounts.token_program,
+        &ctx.accounts.system_program,
+    );
+
+    pay_bet(
+        &mut ctx.accounts.round,
+        &mut ctx.accounts.bet,
+        ctx.accounts.config.refund_fees,
+        &stake,
+        &ctx.accounts.bettor.to_account_info(),
+        ctx.accounts.bettor_token.as_ref(),
+        ctx.accounts.treasury.as_ref().map(|treasury| treasury.to_account_info()),
+        ctx.accounts.treasury_token.as_ref(),
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,128 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::errors::LeftRightError;
+use crate::events::PayoutClaimed;
+use crate::merkle;
+use crate::stake::Stake;
+use crate::state::{PayoutRoot, Round, RoundStatus};
+
+#[derive(Accounts)]
+pub struct ClaimWithProof<'info> {
+    #[account(
+        mut,
+        seeds = [Round::SEED, round.round_id.to_le_bytes().as_ref()],
+        bump = round.bump,
+    )]
+    pub round: Account<'info, Round>,
+
+    #[account(
+        mut,
+        seeds = [PayoutRoot::SEED, round.round_id.to_le_bytes().as_ref()],
+        bump = payout_root.bump,
+    )]
+    pub payout_root: Account<'info, PayoutRoot>,
+
+    /// Round vault PDA holding pool funds
+    /// CHECK: PDA owned by program
+    #[account(
+        mut,
+        seeds = [b"vault", round.round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    /// Bettor claiming their payout
+    #[account(mut)]
+    pub bettor: Signer<'info>,
+
+    /// Stake mint of a token round
+    #[account(
+        constraint = round.stake_mint == Some(stake_mint.key()) @ LeftRightError::InvalidStakeMint
+    )]
+    pub stake_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    /// Token account of a token round the vault PDA holds bets in
+    #[account(
+        mut,
+        seeds = [Round::TOKEN_VAULT_SEED, round.round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub vault_token: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Bettor's token account receiving the payout (token rounds)
+    #[account(mut)]
+    pub bettor_token: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Claim a payout from a round settled by merkle root, proving the leaf
+/// (index, bettor, amount) is in the posted tree, until the claim deadline.
+/// The round is settled once every leaf has been claimed.
+pub fn handler(
+    ctx: Context<ClaimWithProof>,
+    index: u32,
+    amount: u64,
+    proof: Vec<[u8; 32]>,
+) -> Result<()> {
+    let round = &mut ctx.accounts.round;
+    let payout_root = &mut ctx.accounts.payout_root;
+    let bettor = ctx.accounts.bettor.key();
+
+    require!(round.status == RoundStatus::Claiming, LeftRightError::RoundNotClaiming);
+    require!(
+        Clock::get()?.unix_timestamp < payout_root.claim_deadline,
+        LeftRightError::ClaimDeadlinePassed
+    );
+    require!(index < payout_root.leaf_count, LeftRightError::InvalidMerkleProof);
+    require!(!payout_root.is_claimed(index), LeftRightError::PayoutAlreadyProcessed);
+    require!(
+        merkle::verify(
+            &proof,
+            &payout_root.merkle_root,
+            merkle::payout_leaf(index, &bettor, amount),
+        ),
+        LeftRightError::InvalidMerkleProof
+    );
+
+    // Claims can never draw more than the posted total
+    payout_root.claimed_amount = payout_root.claimed_amount
+        .checked_add(amount)
+        .filter(|claimed| *claimed <= payout_root.total_payout)
+        .ok_or(LeftRightError::InvalidPayoutRoot)?;
+    payout_root.claimed_count = payout_root.claimed_count
+        .checked_add(1)
+        .ok_or(LeftRightError::MathOverflow)?;
+    payout_root.set_claimed(index);
+
+    let stake = Stake::new(
+        round,
+        &ctx.accounts.vault,
+        ctx.bumps.vault,
+        &ctx.accounts.stake_mint,
+        &ctx.accounts.vault_token,
+        &ctx.accounts.token_program,
+        &ctx.accounts.system_program,
+    );
+    stake.pay_out(
+        &ctx.accounts.bettor.to_account_info(),
+        ctx.accounts.bettor_token.as_ref(),
+        amount,
+    )?;
+
+    if payout_root.claimed_count >= payout_root.leaf_count {
+        round.status = RoundStatus::Settled;
+    }
+
+    emit!(PayoutClaimed {
+        round_id: round.round_id,
+        bettor,
+        index,
+        amount,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::LeftRightError;
+use crate::events::BetClosed;
+use crate::state::{Bet, Round, RoundStatus};
+
+#[derive(Accounts)]
+pub struct CloseBet<'info> {
+    #[account(
+        mut,
+        seeds = [Round::SEED, round.round_id.to_le_bytes().as_ref()],
+        bump = round.bump,
+    )]
+    pub round: Account<'info, Round>,
+
+    #[account(
+        mut,
+        close = bettor,
+        seeds = [
+            Bet::SEED,
+            round.round_id.to_le_bytes().as_ref(),
+            bet.bettor.as_ref()
+        ],
+        bump = bet.bump,
+        constraint = bet.round_id == round.round_id,
+    )]
+    pub bet: Account<'info, Bet>,
+
+    /// Bettor receiving the bet account's rent
+    /// CHECK: Validated against bet record
+    #[account(
+        mut,
+        constraint = bettor.key() == bet.bettor @ LeftRightError::Unauthorized
+    )]
+    pub bettor: UncheckedAccount<'info>,
+}
+
+/// Close a paid-out bet and return its rent to the bettor (permissionless)
+pub fn handler(ctx: Context<CloseBet>) -> Result<()> {
+    let round = &mut ctx.accounts.round;
+    let bet = &ctx.accounts.bet;
+
+    // Bets of a round settled by merkle root are paid through their claims,
+    // so a settled round has nothing left to pay on any bet
+    require!(
+        bet.paid_out || round.status == RoundStatus::Settled,
+        LeftRightError::PayoutNotProcessed
+    );
+    // Closing would drop the record of a fee refund still owed
+    require!(bet.fee_owed == 0, LeftRightError::FeeRefundOwed);
+
+    round.bets_closed = round.bets_closed
+        .checked_add(1)
+        .ok_or(LeftRightError::MathOverflow)?;
+
+    emit!(BetClosed {
+        round_id: round.round_id,
+        bettor: bet.bettor,
+        bet_index: bet.bet_index,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::LeftRightError;
+use crate::events::PayoutRootClosed;
+use crate::state::{Config, PayoutRoot, Round, RoundStatus};
+
+#[derive(Accounts)]
+pub struct ClosePayoutRoot<'info> {
+    #[account(
+        seeds = [Config::SEED],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [Round::SEED, round.round_id.to_le_bytes().as_ref()],
+        bump = round.bump,
+    )]
+    pub round: Account<'info, Round>,
+
+    #[account(
+        mut,
+        close = admin,
+        seeds = [PayoutRoot::SEED, round.round_id.to_le_bytes().as_ref()],
+        bump = payout_root.bump,
+    )]
+    pub payout_root: Account<'info, PayoutRoot>,
+
+    /// Admin who paid for the payout root, receiving its rent
+    /// CHECK: Validated against config
+    #[account(
+        mut,
+        constraint = admin.key() == config.admin @ LeftRightError::Unauthorized
+    )]
+    pub admin: UncheckedAccount<'info>,
+}
+
+/// Finalize a round settled by merkle root and close its payout root,
+/// returning the rent to the admin (permissionless)
+///
+/// Possible once every leaf is claimed, or once the claim deadline has
+/// passed: the round is then settled, so its bets can be closed and whatever
+/// was left unclaimed is swept to the treasury by `sweep_vault`.
+pub fn handler(ctx: Context<ClosePayoutRoot>) -> Result<()> {
+    let round = &mut ctx.accounts.round;
+    let payout_root = &ctx.accounts.payout_root;
+
+    if round.status == RoundStatus::Claiming {
+        require!(
+            Clock::get()?.unix_timestamp >= payout_root.claim_deadline,
+            LeftRightError::ClaimDeadlineNotPassed
+        );
+        round.status = RoundStatus::Settled;
+    }
+    require!(round.status == RoundStatus::Settled, LeftRightError::RoundNotClaiming);
+
+    emit!(PayoutRootClosed {
+        round_id: round.round_id,
+        unclaimed_amount: payout_root.total_payout.saturating_sub(payout_root.claimed_amount),
+    });
+
+    Ok(())
+}
@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::LeftRightError;
+use crate::events::PriceSamplesClosed;
+use crate::state::{PriceSamples, Round, RoundStatus};
+
+#[derive(Accounts)]
+pub struct ClosePriceSamples<'info> {
+    #[account(
+        seeds = [Round::SEED, round.round_id.to_le_bytes().as_ref()],
+        bump = round.bump,
+    )]
+    pub round: Account<'info, Round>,
+
+    #[account(
+        mut,
+        close = payer,
+        seeds = [PriceSamples::SEED, round.round_id.to_le_bytes().as_ref()],
+        bump = price_samples.bump,
+    )]
+    pub price_samples: Account<'info, PriceSamples>,
+
+    /// Cranker who paid for the sample buffer, receiving its rent
+    /// CHECK: Validated against the sample buffer record
+    #[account(
+        mut,
+        constraint = payer.key() == price_samples.payer @ LeftRightError::Unauthorized
+    )]
+    pub payer: UncheckedAccount<'info>,
+}
+
+/// Close a round's TWAP sample buffer once the round is past settlement,
+/// returning the rent to whoever created it (permissionless)
+pub fn handler(ctx: Context<ClosePriceSamples>) -> Result<()> {
+    let round = &ctx.accounts.round;
+
+    require!(
+        round.status != RoundStatus::Open && round.status != RoundStatus::Locked,
+        LeftRightError::RoundNotFinished
+    );
+
+    emit!(PriceSamplesClosed {
+        round_id: round.round_id,
+        payer: ctx.accounts.payer.key(),
+    });
+
+    Ok(())
+}
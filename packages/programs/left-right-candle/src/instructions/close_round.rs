@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::LeftRightError;
+use crate::events::RoundClosed;
+use crate::state::{Config, Round};
+
+#[derive(Accounts)]
+pub struct CloseRound<'info> {
+    #[account(
+        seeds = [Config::SEED],
+        bump = config.bump,
+        has_one = admin @ LeftRightError::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        close = admin,
+        seeds = [Round::SEED, round.round_id.to_le_bytes().as_ref()],
+        bump = round.bump,
+    )]
+    pub round: Account<'info, Round>,
+
+    /// Round vault PDA, must have been swept
+    /// CHECK: PDA owned by program
+    #[account(
+        seeds = [b"vault", round.round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    /// Vault token account of a token round, must have been closed by the
+    /// sweep (never created for SOL rounds)
+    /// CHECK: PDA owned by program, only its lamports are read
+    #[account(
+        seeds = [Round::TOKEN_VAULT_SEED, round.round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub vault_token: UncheckedAccount<'info>,
+
+    /// Admin who paid for the round account
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
+
+/// Close a finished round once all its bets are closed and its vault swept,
+/// returning the rent to the admin
+pub fn handler(ctx: Context<CloseRound>) -> Result<()> {
+    let round = &ctx.accounts.round;
+
+    require!(round.is_finished(), LeftRightError::RoundNotFinished);
+    require!(round.bets_closed >= round.bet_count, LeftRightError::BetsNotClosed);
+    require!(
+        ctx.accounts.vault.lamports() == 0 && ctx.accounts.vault_token.lamports() == 0,
+        LeftRightError::VaultNotEmpty
+    );
+
+    emit!(RoundClosed {
+        round_id: round.round_id,
+    });
+
+    Ok(())
+}
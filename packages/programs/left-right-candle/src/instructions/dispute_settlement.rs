@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+use crate::errors::LeftRightError;
+use crate::events::SettlementDisputed;
+use crate::state::{Round, RoundStatus};
+
+#[derive(Accounts)]
+pub struct DisputeSettlement<'info> {
+    #[account(
+        mut,
+        seeds = [Round::SEED, round.round_id.to_le_bytes().as_ref()],
+        bump = round.bump,
+    )]
+    pub round: Account<'info, Round>,
+
+    /// Bond vault PDA holding settlement bonds for this round
+    /// CHECK: PDA owned by program
+    #[account(
+        mut,
+        seeds = [Round::BOND_SEED, round.round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub bond_vault: SystemAccount<'info>,
+
+    /// Anyone can dispute by matching the proposer's bond
+    #[account(mut)]
+    pub disputer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Dispute a proposed end price during the challenge window
+pub fn handler(ctx: Context<DisputeSettlement>) -> Result<()> {
+    let round = &mut ctx.accounts.round;
+    let clock = Clock::get()?;
+
+    require!(round.status == RoundStatus::Proposed, LeftRightError::RoundNotProposed);
+    require!(
+        clock.unix_timestamp < round.challenge_end_time,
+        LeftRightError::ChallengeWindowClosed
+    );
+
+    // Counter-bond matches the proposer's bond
+    system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.disputer.to_account_info(),
+                to: ctx.accounts.bond_vault.to_account_info(),
+            },
+        ),
+        round.settlement_bond,
+    )?;
+
+    round.disputer = Some(ctx.accounts.disputer.key());
+    round.status = RoundStatus::Disputed;
+
+    emit!(SettlementDisputed {
+        round_id: round.round_id,
+        disputer: ctx.accounts.disputer.key(),
+        bond: round.settlement_bond,
+    });
+
+    Ok(())
+}
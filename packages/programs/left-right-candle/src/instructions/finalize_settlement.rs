@@ -0,0 +1,121 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+use crate::errors::LeftRightError;
+use crate::events::RoundSettled;
+use crate::instructions::settle_round::roll_over_pool;
+use crate::state::{Config, Round, RoundStatus};
+
+#[derive(Accounts)]
+pub struct FinalizeSettlement<'info> {
+    #[account(
+        mut,
+        seeds = [Config::SEED],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [Round::SEED, round.round_id.to_le_bytes().as_ref()],
+        bump = round.bump,
+    )]
+    pub round: Account<'info, Round>,
+
+    /// Bond vault PDA holding settlement bonds for this round
+    /// CHECK: PDA owned by program
+    #[account(
+        mut,
+        seeds = [Round::BOND_SEED, round.round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub bond_vault: SystemAccount<'info>,
+
+    /// Proposer receiving their bond back
+    /// CHECK: Validated against round record
+    #[account(
+        mut,
+        constraint = proposer.key() == round.proposer @ LeftRightError::Unauthorized
+    )]
+    pub proposer: UncheckedAccount<'info>,
+
+    /// Round vault PDA holding the pool
+    /// CHECK: PDA owned by program
+    #[account(
+        mut,
+        seeds = [b"vault", round.round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    /// Vault of the next round to start, receives the pool of a rolled-over tie
+    /// CHECK: PDA owned by program
+    #[account(
+        mut,
+        seeds = [b"vault", config.round_counter.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub next_vault: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Finalize an undisputed proposal once the challenge window has passed
+/// (permissionless). Returns the proposer's bond and starts payouts.
+pub fn handler(ctx: Context<FinalizeSettlement>) -> Result<()> {
+    let round = &mut ctx.accounts.round;
+    let clock = Clock::get()?;
+
+    require!(round.status == RoundStatus::Proposed, LeftRightError::RoundNotProposed);
+    require!(
+        clock.unix_timestamp >= round.challenge_end_time,
+        LeftRightError::ChallengeWindowOpen
+    );
+
+    // Return proposer bond
+    let round_id_bytes = round.round_id.to_le_bytes();
+    let bond_seeds = &[
+        Round::BOND_SEED,
+        round_id_bytes.as_ref(),
+        &[ctx.bumps.bond_vault],
+    ];
+
+    system_program::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.bond_vault.to_account_info(),
+                to: ctx.accounts.proposer.to_account_info(),
+            },
+            &[bond_seeds],
+        ),
+        round.settlement_bond,
+    )?;
+
+    let rollover_amount = round.rollover_amount();
+    roll_over_pool(
+        round,
+        &mut ctx.accounts.config,
+        rollover_amount,
+        ctx.accounts.vault.to_account_info(),
+        ctx.bumps.vault,
+        ctx.accounts.next_vault.to_account_info(),
+        ctx.accounts.system_program.to_account_info(),
+    )?;
+
+    round.status = round.payout_status();
+
+    emit!(RoundSettled {
+        round_id: round.round_id,
+        settler: round.proposer,
+        price_sources: round.price_sources.clone(),
+        start_price: round.start_price,
+        end_price: round.end_price,
+        end_readings: round.end_readings,
+        winning_side: round.outcome(),
+        total_pool: round.total_pool(),
+        winning_pool: round.winning_pool(),
+    });
+
+    Ok(())
+}
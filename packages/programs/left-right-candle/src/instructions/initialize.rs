@@ -1,7 +1,7 @@
 use anchor_lang::prelude::*;
 use crate::errors::LeftRightError;
 use crate::events::ConfigUpdated;
-use crate::state::Config;
+use crate::state::{Config, TiePolicy, WeightCurve};
 
 #[derive(Accounts)]
 pub struct Initialize<'info> {
@@ -42,6 +42,29 @@ pub fn handler(
     config.max_bet_lamports = max_bet_lamports;
     config.treasury = ctx.accounts.treasury.key();
     config.round_counter = 0;
+    config.max_price_age_secs = Config::DEFAULT_MAX_PRICE_AGE_SECS;
+    config.max_conf_bps = Config::DEFAULT_MAX_CONF_BPS;
+    config.price_signers = Vec::new();
+    config.signer_threshold = 0;
+    config.dispute_window_secs = 0;
+    config.settlement_bond_lamports = 0;
+    config.twap_min_samples = Config::DEFAULT_TWAP_MIN_SAMPLES;
+    config.twap_min_spacing_secs = Config::DEFAULT_TWAP_MIN_SPACING_SECS;
+    config.tie_policy = TiePolicy::Refund;
+    config.rollover_pool = 0;
+    config.refund_fees = false;
+    config.void_grace_secs = Config::DEFAULT_VOID_GRACE_SECS;
+    config.min_betting_secs = Config::DEFAULT_MIN_DURATION_SECS;
+    config.max_betting_secs = Config::DEFAULT_MAX_DURATION_SECS;
+    config.min_waiting_secs = Config::DEFAULT_MIN_DURATION_SECS;
+    config.max_waiting_secs = Config::DEFAULT_MAX_DURATION_SECS;
+    config.exit_penalty_bps = Config::DEFAULT_EXIT_PENALTY_BPS;
+    config.exit_penalty_to_treasury = false;
+    config.weight_curve = WeightCurve::default();
+    config.max_round_exposure_lamports = 0;
+    config.max_daily_exposure_lamports = 0;
+    config.dynamic_max_bet_bps = 0;
+    config.dynamic_max_bet_floor_lamports = 0;
     config.bump = ctx.bumps.config;
 
     emit!(ConfigUpdated {
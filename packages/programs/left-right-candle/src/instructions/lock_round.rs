@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::LeftRightError;
+use crate::events::RoundLocked;
+use crate::state::{Round, RoundStatus, Side};
+
+#[derive(Accounts)]
+pub struct LockRound<'info> {
+    #[account(
+        mut,
+        seeds = [Round::SEED, round.round_id.to_le_bytes().as_ref()],
+        bump = round.bump,
+    )]
+    pub round: Account<'info, Round>,
+}
+
+/// Close betting once the betting window has passed (permissionless)
+///
+/// Pools can't change after this, so the event carries the final pools and
+/// the implied payout per side.
+pub fn handler(ctx: Context<LockRound>) -> Result<()> {
+    let round = &mut ctx.accounts.round;
+    let clock = Clock::get()?;
+
+    require!(round.status == RoundStatus::Open, LeftRightError::RoundNotOpen);
+    require!(
+        clock.unix_timestamp >= round.betting_end_time,
+        LeftRightError::BettingPeriodActive
+    );
+
+    round.status = RoundStatus::Locked;
+
+    emit!(RoundLocked {
+        round_id: round.round_id,
+        locked_at: clock.unix_timestamp,
+        left_pool: round.left_pool,
+        right_pool: round.right_pool,
+        left_weighted_pool: round.left_weighted_pool,
+        right_weighted_pool: round.right_weighted_pool,
+        bonus_pool: round.bonus_pool,
+        bet_count: round.bet_count,
+        left_odds_bps: round.implied_odds_bps(Side::Left),
+        right_odds_bps: round.implied_odds_bps(Side::Right),
+    });
+
+    Ok(())
+}
@@ -1,11 +1,78 @@
+// Every instruction module exposes a `handler`; lib.rs always calls them by path.
+#![allow(ambiguous_glob_reexports)]
+
+pub mod cancel_round;
+pub mod claim;
+pub mod claim_with_proof;
+pub mod close_bet;
+pub mod close_payout_root;
+pub mod close_price_samples;
+pub mod close_round;
+pub mod dispute_settlement;
+pub mod finalize_settlement;
 pub mod initialize;
+pub mod lock_round;
 pub mod place_bet;
+pub mod post_payout_root;
 pub mod process_payout;
+pub mod process_payouts_batch;
+pub mod record_price_sample;
+pub mod refund_bet;
+pub mod resolve_dispute;
+pub mod set_dispute_config;
+pub mod set_duration_bounds;
+pub mod set_dynamic_max_bet;
+pub mod set_exit_penalty;
+pub mod set_exposure_limits;
+pub mod set_mint_config;
+pub mod set_oracle_config;
+pub mod set_price_signers;
+pub mod set_refund_fees;
+pub mod set_tie_policy;
+pub mod set_twap_config;
+pub mod set_void_grace_period;
+pub mod set_weight_curve;
+pub mod settle_fee_refund;
 pub mod settle_round;
 pub mod start_round;
+pub mod sweep_vault;
+pub mod void_round;
+pub mod withdraw_bet;
 
+pub use cancel_round::*;
+pub use claim::*;
+pub use claim_with_proof::*;
+pub use close_bet::*;
+pub use close_payout_root::*;
+pub use close_price_samples::*;
+pub use close_round::*;
+pub use dispute_settlement::*;
+pub use finalize_settlement::*;
 pub use initialize::*;
+pub use lock_round::*;
 pub use place_bet::*;
+pub use post_payout_root::*;
 pub use process_payout::*;
+pub use process_payouts_batch::*;
+pub use record_price_sample::*;
+pub use refund_bet::*;
+pub use resolve_dispute::*;
+pub use set_dispute_config::*;
+pub use set_duration_bounds::*;
+pub use set_dynamic_max_bet::*;
+pub use set_exit_penalty::*;
+pub use set_exposure_limits::*;
+pub use set_mint_config::*;
+pub use set_oracle_config::*;
+pub use set_price_signers::*;
+pub use set_refund_fees::*;
+pub use set_tie_policy::*;
+pub use set_twap_config::*;
+pub use set_void_grace_period::*;
+pub use set_weight_curve::*;
+pub use settle_fee_refund::*;
 pub use settle_round::*;
 pub use start_round::*;
+pub use sweep_vault::*;
+pub use void_round::*;
+pub use withdraw_bet::*;
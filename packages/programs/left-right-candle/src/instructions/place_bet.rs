@@ -1,9 +1,10 @@
 use anchor_lang::prelude::*;
-use anchor_lang::system_program;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 use crate::errors::LeftRightError;
 use crate::events::BetPlaced;
-use crate::state::{Bet, Config, Round, RoundStatus, Side};
+use crate::stake::Stake;
+use crate::state::{Bet, Config, MintConfig, Round, RoundStatus, Side, UserStats};
 
 #[derive(Accounts)]
 pub struct PlaceBet<'info> {
@@ -20,19 +21,48 @@ pub struct PlaceBet<'info> {
     )]
     pub round: Account<'info, Round>,
 
+    /// Bettor's position in the round, created on the first bet and topped
+    /// up by later ones. Keyed by bettor, so concurrent bets from different
+    /// wallets never derive the same address.
     #[account(
-        init,
+        init_if_needed,
         payer = bettor,
         space = Bet::LEN,
         seeds = [
             Bet::SEED,
             round.round_id.to_le_bytes().as_ref(),
-            round.bet_count.to_le_bytes().as_ref()
+            bettor.key().as_ref()
         ],
         bump
     )]
     pub bet: Account<'info, Bet>,
 
+    /// Bettor's SOL volume stats, for the rolling 24h exposure cap (SOL
+    /// rounds)
+    #[account(
+        init_if_needed,
+        payer = bettor,
+        space = UserStats::LEN,
+        seeds = [UserStats::SEED, bettor.key().as_ref()],
+        bump
+    )]
+    pub user_stats: Option<Account<'info, UserStats>>,
+
+    /// Bettor's volume stats in the stake mint, for the mint's rolling 24h
+    /// exposure cap (token rounds)
+    #[account(
+        init_if_needed,
+        payer = bettor,
+        space = UserStats::LEN,
+        seeds = [
+            UserStats::SEED,
+            bettor.key().as_ref(),
+            round.stake_mint.unwrap_or_default().as_ref()
+        ],
+        bump
+    )]
+    pub mint_user_stats: Option<Account<'info, UserStats>>,
+
     /// Round vault PDA to hold bets (after fees)
     /// CHECK: PDA owned by program
     #[account(
@@ -58,10 +88,60 @@ pub struct PlaceBet<'info> {
     #[account(mut)]
     pub referrer: Option<UncheckedAccount<'info>>,
 
+    /// Bet limits of a token round's stake mint
+    #[account(
+        constraint = round.stake_mint == Some(mint_config.mint) @ LeftRightError::InvalidStakeMint
+    )]
+    pub mint_config: Option<Account<'info, MintConfig>>,
+
+    /// Stake mint of a token round
+    #[account(
+        constraint = round.stake_mint == Some(stake_mint.key()) @ LeftRightError::InvalidStakeMint
+    )]
+    pub stake_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    /// Token account of a token round the vault PDA holds bets in
+    #[account(
+        mut,
+        seeds = [Round::TOKEN_VAULT_SEED, round.round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub vault_token: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Bettor's token account the bet is paid from (token rounds)
+    #[account(mut)]
+    pub bettor_token: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Treasury's token account receiving the fee (token rounds)
+    #[account(mut)]
+    pub treasury_token: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Referrer's token account receiving their cut (token rounds)
+    #[account(mut)]
+    pub referrer_token: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+
     pub system_program: Program<'info, System>,
 }
 
-pub fn handler(ctx: Context<PlaceBet>, side: u8, amount_lamports: u64) -> Result<()> {
+/// Place a bet, or top up the bettor's existing position on the same side
+///
+/// `min_multiplier_bps` guards against the pool moving before the bet lands:
+/// the bet is rejected if its implied payout per lamport if its side wins,
+/// computed after it is added to the pools, is below this bound
+/// (e.g. 15_000 = 1.5x).
+///
+/// In a token round `amount_lamports` is in the stake mint's base units and
+/// the bet limits come from its `MintConfig`. With a Token-2022 transfer fee
+/// the position is credited with the amount the vault actually received.
+/// Bets in each stake mint count towards their own rolling 24h cap.
+pub fn handler(
+    ctx: Context<PlaceBet>,
+    side: u8,
+    amount_lamports: u64,
+    min_multiplier_bps: Option<u64>,
+) -> Result<()> {
     let config = &ctx.accounts.config;
     let round = &mut ctx.accounts.round;
     let clock = Clock::get()?;
@@ -77,9 +157,59 @@ pub fn handler(ctx: Context<PlaceBet>, side: u8, amount_lamports: u64) -> Result
         LeftRightError::BettingPeriodEnded
     );
 
-    // Validate bet amount
-    require!(amount_lamports >= config.min_bet_lamports, LeftRightError::BetTooSmall);
-    require!(amount_lamports <= config.max_bet_lamports, LeftRightError::BetTooLarge);
+    // A fresh position has no bettor yet; an existing one can only grow
+    let position = &ctx.accounts.bet;
+    let is_new_position = position.bettor == Pubkey::default();
+    require!(!position.withdrawn, LeftRightError::BetWithdrawn);
+    require!(
+        is_new_position || position.side == side,
+        LeftRightError::PositionSideMismatch
+    );
+
+    // Validate bet amount, in the round's stake units
+    let limits = match round.stake_mint {
+        Some(_) => ctx.accounts.mint_config
+            .as_ref()
+            .ok_or(LeftRightError::InvalidStakeMint)?
+            .bet_limits(),
+        None => config.bet_limits(),
+    };
+    require!(amount_lamports >= limits.min_bet, LeftRightError::BetTooSmall);
+    require!(amount_lamports <= limits.max_bet, LeftRightError::BetTooLarge);
+    require!(
+        amount_lamports <= config.dynamic_max_bet(round.total_pool(), limits.dynamic_max_bet_floor),
+        LeftRightError::BetExceedsPoolLimit
+    );
+
+    // Per-wallet exposure caps: the whole position in this round, and all
+    // bets over the last 24 hours
+    let position_original_amount = position.original_amount
+        .checked_add(amount_lamports)
+        .ok_or(LeftRightError::MathOverflow)?;
+    require!(
+        limits.max_round_exposure == 0
+            || position_original_amount <= limits.max_round_exposure,
+        LeftRightError::RoundExposureExceeded
+    );
+
+    let (user_stats, user_stats_bump) = match round.stake_mint {
+        Some(_) => (ctx.accounts.mint_user_stats.as_mut(), ctx.bumps.mint_user_stats),
+        None => (ctx.accounts.user_stats.as_mut(), ctx.bumps.user_stats),
+    };
+    let (user_stats, user_stats_bump) = user_stats
+        .zip(user_stats_bump)
+        .ok_or(LeftRightError::MissingUserStats)?;
+    user_stats.user = ctx.accounts.bettor.key();
+    user_stats.bump = user_stats_bump;
+    let daily_volume = user_stats
+        .rolling_volume(clock.unix_timestamp)
+        .checked_add(amount_lamports)
+        .ok_or(LeftRightError::MathOverflow)?;
+    require!(
+        limits.max_daily_exposure == 0 || daily_volume <= limits.max_daily_exposure,
+        LeftRightError::DailyExposureExceeded
+    );
+    user_stats.record(clock.unix_timestamp, amount_lamports);
 
     // ============================================
     // FEE CALCULATION (taken upfront from bet)
@@ -106,8 +236,8 @@ pub fn handler(ctx: Context<PlaceBet>, side: u8, amount_lamports: u64) -> Result
         0
     };
 
-    // Amount that goes into the pool (after fees)
-    let pool_amount = amount_lamports
+    // Amount sent to the pool (after fees)
+    let deposit_amount = amount_lamports
         .checked_sub(treasury_fee)
         .and_then(|v| v.checked_sub(referrer_fee))
         .ok_or(LeftRightError::MathOverflow)?;
@@ -116,56 +246,52 @@ pub fn handler(ctx: Context<PlaceBet>, side: u8, amount_lamports: u64) -> Result
     // TRANSFERS
     // ============================================
 
+    let stake = Stake::new(
+        round,
+        &ctx.accounts.vault,
+        ctx.bumps.vault,
+        &ctx.accounts.stake_mint,
+        &ctx.accounts.vault_token,
+        &ctx.accounts.token_program,
+        &ctx.accounts.system_program,
+    );
+    let bettor = ctx.accounts.bettor.to_account_info();
+    let bettor_token = ctx.accounts.bettor_token.as_ref();
+
     // 1. Transfer treasury fee
-    if treasury_fee > 0 {
-        system_program::transfer(
-            CpiContext::new(
-                ctx.accounts.system_program.to_account_info(),
-                system_program::Transfer {
-                    from: ctx.accounts.bettor.to_account_info(),
-                    to: ctx.accounts.treasury.to_account_info(),
-                },
-            ),
-            treasury_fee,
-        )?;
-    }
+    stake.transfer(
+        &bettor,
+        bettor_token,
+        &ctx.accounts.treasury.to_account_info(),
+        ctx.accounts.treasury_token.as_ref(),
+        treasury_fee,
+    )?;
 
     // 2. Transfer referrer fee (if applicable)
     if referrer_fee > 0 {
         if let Some(ref referrer_account) = ctx.accounts.referrer {
-            system_program::transfer(
-                CpiContext::new(
-                    ctx.accounts.system_program.to_account_info(),
-                    system_program::Transfer {
-                        from: ctx.accounts.bettor.to_account_info(),
-                        to: referrer_account.to_account_info(),
-                    },
-                ),
+            stake.transfer(
+                &bettor,
+                bettor_token,
+                &referrer_account.to_account_info(),
+                ctx.accounts.referrer_token.as_ref(),
                 referrer_fee,
             )?;
         }
     }
 
-    // 3. Transfer remaining amount to vault (the actual pool)
-    system_program::transfer(
-        CpiContext::new(
-            ctx.accounts.system_program.to_account_info(),
-            system_program::Transfer {
-                from: ctx.accounts.bettor.to_account_info(),
-                to: ctx.accounts.vault.to_account_info(),
-            },
-        ),
-        pool_amount,
-    )?;
+    // 3. Transfer remaining amount to vault (the actual pool). The pool is
+    // credited with what the vault received, net of any Token-2022 transfer fee.
+    let pool_amount = stake.deposit(&bettor, bettor_token, deposit_amount)?;
 
     // ============================================
     // CALCULATE WEIGHT BASED ON BET TIMING
     // ============================================
     let bet_time = clock.unix_timestamp;
-    let weight = Bet::calculate_weight(round.start_time, bet_time);
+    let weight = round.weight_curve.weight_at(round.start_time, round.betting_duration(), bet_time);
 
     // Weighted amount for pool distribution
-    let weighted_amount = (pool_amount as u128 * weight as u128 / 100) as u64;
+    let weighted_amount = Bet::weigh(pool_amount, weight);
 
     // ============================================
     // UPDATE STATE
@@ -192,24 +318,50 @@ pub fn handler(ctx: Context<PlaceBet>, side: u8, amount_lamports: u64) -> Result
         }
     }
 
-    // Create bet record
-    // Store the POOL AMOUNT (what's actually in the pool for payout calculation)
+    // Slippage check on the post-bet implied multiplier
+    if let Some(min_multiplier_bps) = min_multiplier_bps {
+        let multiplier_bps = round.bet_multiplier_bps(
+            side_enum,
+            amount_lamports,
+            pool_amount,
+            weighted_amount,
+        );
+        require!(
+            multiplier_bps >= min_multiplier_bps,
+            LeftRightError::MultiplierBelowMinimum
+        );
+    }
+
+    // Create the position on the first bet
     let bet = &mut ctx.accounts.bet;
-    bet.round_id = round.round_id;
-    bet.bettor = ctx.accounts.bettor.key();
-    bet.side = side;
-    bet.amount = pool_amount;  // Store pool contribution, not original bet
-    bet.original_amount = amount_lamports;  // Store original for reference
-    bet.bet_time = bet_time;  // Store when bet was placed
-    bet.weight = weight;  // Store weight multiplier (scaled by 100)
-    bet.bet_index = round.bet_count;
-    bet.paid_out = false;
-    bet.referrer = referrer_key;
-    bet.bump = ctx.bumps.bet;
-
-    let bet_index = round.bet_count;
-    round.bet_count = round.bet_count
-        .checked_add(1)
+    if is_new_position {
+        bet.round_id = round.round_id;
+        bet.bettor = ctx.accounts.bettor.key();
+        bet.side = side;
+        bet.bet_time = bet_time;  // Store when the position was opened
+        bet.bet_index = round.bet_count;
+        bet.paid_out = false;
+        bet.withdrawn = false;
+        bet.referrer = referrer_key;
+        bet.bump = ctx.bumps.bet;
+
+        round.bet_count = round.bet_count
+            .checked_add(1)
+            .ok_or(LeftRightError::MathOverflow)?;
+    }
+
+    // Accumulate the POOL AMOUNT (what's actually in the pool for payout
+    // calculation) and this top-up's weighted amount, exactly as added to
+    // the round's weighted pool
+    bet.amount = bet.amount
+        .checked_add(pool_amount)
+        .ok_or(LeftRightError::MathOverflow)?;
+    bet.original_amount = position_original_amount;  // Store original for reference
+    bet.treasury_fee = bet.treasury_fee  // Refundable if the round has no contest
+        .checked_add(treasury_fee)
+        .ok_or(LeftRightError::MathOverflow)?;
+    bet.weighted_amount = bet.weighted_amount
+        .checked_add(weighted_amount)
         .ok_or(LeftRightError::MathOverflow)?;
 
     emit!(BetPlaced {
@@ -220,7 +372,9 @@ pub fn handler(ctx: Context<PlaceBet>, side: u8, amount_lamports: u64) -> Result
         original_amount: amount_lamports,
         treasury_fee,
         referrer_fee,
-        bet_index,
+        bet_index: bet.bet_index,
+        weight,
+        position_amount: bet.amount,
         referrer: referrer_key,
     });
 
@@ -0,0 +1,137 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::errors::LeftRightError;
+use crate::events::PayoutRootPosted;
+use crate::stake::Stake;
+use crate::state::{Config, PayoutRoot, Round, RoundStatus};
+
+#[derive(Accounts)]
+#[instruction(merkle_root: [u8; 32], total_payout: u64, leaf_count: u32, claim_window_secs: i64)]
+pub struct PostPayoutRoot<'info> {
+    #[account(
+        seeds = [Config::SEED],
+        bump = config.bump,
+        has_one = admin @ LeftRightError::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [Round::SEED, round.round_id.to_le_bytes().as_ref()],
+        bump = round.bump,
+    )]
+    pub round: Account<'info, Round>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = PayoutRoot::space(leaf_count),
+        seeds = [PayoutRoot::SEED, round.round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub payout_root: Account<'info, PayoutRoot>,
+
+    /// Round vault PDA holding pool funds
+    /// CHECK: PDA owned by program
+    #[account(
+        seeds = [b"vault", round.round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    /// Stake mint of a token round
+    #[account(
+        constraint = round.stake_mint == Some(stake_mint.key()) @ LeftRightError::InvalidStakeMint
+    )]
+    pub stake_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    /// Token account of a token round the vault PDA holds bets in
+    #[account(
+        seeds = [Round::TOKEN_VAULT_SEED, round.round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub vault_token: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Settle a large round's payouts by merkle root instead of per-bet payouts
+///
+/// The root covers one (index, bettor, payout) leaf per winning bet,
+/// computed off-chain from the round's `Bet` accounts with the same formula
+/// as `process_payout`. Only possible before any bet has been paid, and the
+/// total must be covered by the vault, less a SOL vault's rent reserve.
+/// Winners then claim with `claim_with_proof` for `claim_window_secs`; after
+/// that whatever is left unclaimed goes to the treasury sweep (see
+/// `close_payout_root`).
+pub fn handler(
+    ctx: Context<PostPayoutRoot>,
+    merkle_root: [u8; 32],
+    total_payout: u64,
+    leaf_count: u32,
+    claim_window_secs: i64,
+) -> Result<()> {
+    let round = &mut ctx.accounts.round;
+    let clock = Clock::get()?;
+
+    require!(round.status == RoundStatus::Settling, LeftRightError::RoundNotSettling);
+    require!(
+        round.payouts_processed == 0
+            && leaf_count > 0
+            && leaf_count <= PayoutRoot::MAX_LEAVES
+            && leaf_count <= round.bet_count,
+        LeftRightError::InvalidPayoutRoot
+    );
+    require!(claim_window_secs > 0, LeftRightError::InvalidPayoutRoot);
+
+    let claim_deadline = clock.unix_timestamp
+        .checked_add(claim_window_secs)
+        .ok_or(LeftRightError::MathOverflow)?;
+
+    let stake = Stake::new(
+        round,
+        &ctx.accounts.vault,
+        ctx.bumps.vault,
+        &ctx.accounts.stake_mint,
+        &ctx.accounts.vault_token,
+        &ctx.accounts.token_program,
+        &ctx.accounts.system_program,
+    );
+    // A SOL vault must stay rent-exempt until it is swept, so its rent
+    // reserve can't be claimed
+    let rent_reserve = if stake.is_token() { 0 } else { Rent::get()?.minimum_balance(0) };
+    require!(
+        total_payout <= stake.vault_balance()?.saturating_sub(rent_reserve),
+        LeftRightError::PayoutRootExceedsVault
+    );
+
+    let payout_root = &mut ctx.accounts.payout_root;
+    payout_root.round_id = round.round_id;
+    payout_root.merkle_root = merkle_root;
+    payout_root.total_payout = total_payout;
+    payout_root.claimed_amount = 0;
+    payout_root.leaf_count = leaf_count;
+    payout_root.claimed_count = 0;
+    payout_root.claim_deadline = claim_deadline;
+    payout_root.bump = ctx.bumps.payout_root;
+    payout_root.claimed = vec![0; PayoutRoot::bitmap_len(leaf_count)];
+
+    // Per-bet payouts are closed from here on
+    round.status = RoundStatus::Claiming;
+
+    emit!(PayoutRootPosted {
+        round_id: round.round_id,
+        merkle_root,
+        total_payout,
+        leaf_count,
+        claim_deadline,
+    });
+
+    Ok(())
+}
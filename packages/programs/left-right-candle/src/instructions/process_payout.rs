@@ -1,11 +1,19 @@
 use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 use crate::errors::LeftRightError;
-use crate::events::PayoutProcessed;
-use crate::state::{Bet, Round, RoundStatus};
+use crate::events::{BetRefunded, PayoutProcessed};
+use crate::stake::Stake;
+use crate::state::{Bet, Config, Round, RoundStatus};
 
 #[derive(Accounts)]
 pub struct ProcessPayout<'info> {
+    #[account(
+        seeds = [Config::SEED],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
     #[account(
         mut,
         seeds = [Round::SEED, round.round_id.to_le_bytes().as_ref()],
@@ -18,7 +26,7 @@ pub struct ProcessPayout<'info> {
         seeds = [
             Bet::SEED,
             round.round_id.to_le_bytes().as_ref(),
-            bet.bet_index.to_le_bytes().as_ref()
+            bet.bettor.as_ref()
         ],
         bump = bet.bump,
         constraint = bet.round_id == round.round_id,
@@ -42,6 +50,38 @@ pub struct ProcessPayout<'info> {
     )]
     pub bettor: UncheckedAccount<'info>,
 
+    /// Treasury returning the platform fee on refunds (only when fee
+    /// refunds are enabled)
+    #[account(
+        mut,
+        constraint = treasury.key() == config.treasury @ LeftRightError::Unauthorized
+    )]
+    pub treasury: Option<Signer<'info>>,
+
+    /// Stake mint of a token round
+    #[account(
+        constraint = round.stake_mint == Some(stake_mint.key()) @ LeftRightError::InvalidStakeMint
+    )]
+    pub stake_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    /// Token account of a token round the vault PDA holds bets in
+    #[account(
+        mut,
+        seeds = [Round::TOKEN_VAULT_SEED, round.round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub vault_token: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Bettor's token account receiving the payout (token rounds)
+    #[account(mut)]
+    pub bettor_token: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Treasury's token account returning the fee (token rounds)
+    #[account(mut)]
+    pub treasury_token: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+
     pub system_program: Program<'info, System>,
 }
 
@@ -49,111 +89,113 @@ pub struct ProcessPayout<'info> {
 ///
 /// Payout formula (weighted distribution with guaranteed return):
 ///   1. Winners get their original bet back (minus fee already deducted)
-///   2. Losers' pool is distributed to winners based on weighted share
+///   2. Losers' pool plus any rolled-over bonus is distributed to winners
+///      based on weighted share
 ///
-///   payout = bet.amount + (bet.weighted_amount / winning_weighted_pool) * losing_pool
+///   payout = bet.amount + (bet.weighted_amount / winning_weighted_pool) * (losing_pool + bonus_pool)
 ///
-/// Weight tiers based on bet timing (early bird bonus):
-/// - Hours 0-3: 1.5x weight
-/// - Hours 3-6: 1.3x weight
-/// - Hours 6-9: 1.15x weight
-/// - Hours 9-12: 1.0x weight
+/// Refunding rounds (no contest, or a tie under `TiePolicy::Refund`) and
+/// cancelled rounds return every bet's pool contribution, plus the treasury
+/// fee when fee refunds are enabled. A fee the treasury doesn't co-sign for
+/// is recorded on the bet as owed until `settle_fee_refund` pays it. On a
+/// rolled-over tie nothing is paid: the pool moved to the next round.
+///
+/// Weights come from the round's weight curve based on bet timing (early
+/// bird bonus). The default curve uses quarters of the betting window:
+/// - 1st quarter: 1.5x weight
+/// - 2nd quarter: 1.3x weight
+/// - 3rd quarter: 1.15x weight
+/// - 4th quarter: 1.0x weight
 pub fn handler(ctx: Context<ProcessPayout>) -> Result<()> {
-    let round = &mut ctx.accounts.round;
-    let bet = &mut ctx.accounts.bet;
+    let stake = Stake::new(
+        &ctx.accounts.round,
+        &ctx.accounts.vault,
+        ctx.bumps.vault,
+        &ctx.accounts.stake_mint,
+        &ctx.accounts.vault_token,
+        &ctx.accounts.token_program,
+        &ctx.accounts.system_program,
+    );
+
+    pay_bet(
+        &mut ctx.accounts.round,
+        &mut ctx.accounts.bet,
+        ctx.accounts.config.refund_fees,
+        &stake,
+        &ctx.accounts.bettor.to_account_info(),
+        ctx.accounts.bettor_token.as_ref(),
+        ctx.accounts.treasury.as_ref().map(|treasury| treasury.to_account_info()),
+        ctx.accounts.treasury_token.as_ref(),
+    )?;
+
+    Ok(())
+}
 
+/// Pay what a bet is owed from the vault (see `Round::payout_for`), plus the
+/// treasury fee on refunds when enabled and the treasury signed (recorded as
+/// `Bet::fee_owed` otherwise), then mark it paid and count it.
+/// Returns the amount paid from the vault.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn pay_bet<'info>(
+    round: &mut Round,
+    bet: &mut Bet,
+    refund_fees: bool,
+    stake: &Stake<'_, 'info>,
+    bettor: &AccountInfo<'info>,
+    bettor_token: Option<&InterfaceAccount<'info, TokenAccount>>,
+    treasury: Option<AccountInfo<'info>>,
+    treasury_token: Option<&InterfaceAccount<'info, TokenAccount>>,
+) -> Result<u64> {
     // Validate round is in settling state
     require!(
-        round.status == RoundStatus::Settling,
+        round.status == RoundStatus::Settling || round.is_refunding(),
         LeftRightError::RoundNotSettling
     );
 
     // Check bet hasn't been paid out already
     require!(!bet.paid_out, LeftRightError::PayoutAlreadyProcessed);
 
-    // Get winning side
-    let winning_side = round.winning_side.ok_or(LeftRightError::RoundNotSettling)?;
-    let bet_side = bet.side_enum().ok_or(LeftRightError::InvalidSide)?;
-
-    // Note: Referrer fees were already paid at bet time, nothing to do here
-
-    if bet_side == winning_side {
-        // Get pools based on winning side
-        let (winning_pool, winning_weighted_pool, losing_pool) = match winning_side {
-            crate::state::Side::Left => (
-                round.left_pool,
-                round.left_weighted_pool,
-                round.right_pool,
-            ),
-            crate::state::Side::Right => (
-                round.right_pool,
-                round.right_weighted_pool,
-                round.left_pool,
-            ),
-        };
+    let refunding = round.is_refunding();
+    let payout = round.payout_for(bet)?;
+
+    // Transfer payout from vault to bettor, signed by the vault PDA
+    stake.pay_out(bettor, bettor_token, payout)?;
 
-        // Calculate payout
-        // Step 1: Winner gets their bet back
-        let mut payout = bet.amount;
-
-        // Step 2: Add weighted share of losing pool (if there are losers)
-        if losing_pool > 0 && winning_weighted_pool > 0 {
-            // Calculate this bet's weighted amount
-            let bet_weighted_amount = bet.weighted_amount();
-
-            // Their share of the losers' pool
-            // share = (bet_weighted_amount / winning_weighted_pool) * losing_pool
-            let bonus = (bet_weighted_amount as u128)
-                .checked_mul(losing_pool as u128)
-                .and_then(|v| v.checked_div(winning_weighted_pool as u128))
-                .ok_or(LeftRightError::MathOverflow)? as u64;
-
-            payout = payout
-                .checked_add(bonus)
-                .ok_or(LeftRightError::MathOverflow)?;
-        }
-
-        if payout > 0 {
-            // Transfer payout from vault to bettor using CPI with PDA signer
-            let round_id_bytes = round.round_id.to_le_bytes();
-            let vault_seeds = &[
-                b"vault".as_ref(),
-                round_id_bytes.as_ref(),
-                &[ctx.bumps.vault],
-            ];
-
-            anchor_lang::system_program::transfer(
-                CpiContext::new_with_signer(
-                    ctx.accounts.system_program.to_account_info(),
-                    anchor_lang::system_program::Transfer {
-                        from: ctx.accounts.vault.to_account_info(),
-                        to: ctx.accounts.bettor.to_account_info(),
-                    },
-                    &[vault_seeds],
-                ),
-                payout,
-            )?;
-
-            emit!(PayoutProcessed {
-                round_id: round.round_id,
-                bettor: bet.bettor,
-                amount: payout,
-            });
-        }
+    if refunding && !bet.withdrawn {
+        // Treasury fee comes back from the treasury itself when it co-signs;
+        // otherwise it is recorded as owed on the bet instead of holding up
+        // the refund
+        let fee_due = round.fee_refund_for(bet, refund_fees);
+
+        let fee_refund = match treasury {
+            Some(treasury) if fee_due > 0 && treasury.is_signer => {
+                stake.transfer(&treasury, treasury_token, bettor, bettor_token, fee_due)?;
+                fee_due
+            }
+            _ => 0,
+        };
+        bet.fee_owed = fee_due - fee_refund;
+
+        emit!(BetRefunded {
+            round_id: round.round_id,
+            bettor: bet.bettor,
+            amount: payout,
+            fee_refund,
+            fee_owed: bet.fee_owed,
+        });
+    } else if payout > 0 {
+        emit!(PayoutProcessed {
+            round_id: round.round_id,
+            bettor: bet.bettor,
+            amount: payout,
+        });
     }
 
     // Mark bet as paid out (even if they lost, to track progress)
     bet.paid_out = true;
 
-    // Update payout counter
-    round.payouts_processed = round.payouts_processed
-        .checked_add(1)
-        .ok_or(LeftRightError::MathOverflow)?;
+    // Update payout counter, settling the round after the last one
+    round.record_payout()?;
 
-    // If all payouts processed, mark round as settled
-    if round.payouts_processed >= round.bet_count {
-        round.status = RoundStatus::Settled;
-    }
-
-    Ok(())
+    Ok(payout)
 }
@@ -0,0 +1,176 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::compute_units::sol_remaining_compute_units;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::errors::LeftRightError;
+use crate::events::PayoutBatchProcessed;
+use crate::instructions::process_payout::pay_bet;
+use crate::stake::Stake;
+use crate::state::{Bet, Config, Round, RoundStatus};
+
+/// Compute units kept in hand for each payout; the batch stops before a
+/// payout that might not fit
+const COMPUTE_UNITS_PER_PAYOUT: u64 = 30_000;
+
+#[derive(Accounts)]
+pub struct ProcessPayoutsBatch<'info> {
+    #[account(
+        seeds = [Config::SEED],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [Round::SEED, round.round_id.to_le_bytes().as_ref()],
+        bump = round.bump,
+    )]
+    pub round: Account<'info, Round>,
+
+    /// Round vault holding pool funds (already fee-adjusted)
+    /// CHECK: PDA owned by program
+    #[account(
+        mut,
+        seeds = [b"vault", round.round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    /// Treasury returning the platform fee on refunds (only when fee
+    /// refunds are enabled)
+    #[account(
+        mut,
+        constraint = treasury.key() == config.treasury @ LeftRightError::Unauthorized
+    )]
+    pub treasury: Option<Signer<'info>>,
+
+    /// Stake mint of a token round
+    #[account(
+        constraint = round.stake_mint == Some(stake_mint.key()) @ LeftRightError::InvalidStakeMint
+    )]
+    pub stake_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    /// Token account of a token round the vault PDA holds bets in
+    #[account(
+        mut,
+        seeds = [Round::TOKEN_VAULT_SEED, round.round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub vault_token: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Treasury's token account returning the fee (token rounds)
+    #[account(mut)]
+    pub treasury_token: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Process payouts for many bets in one instruction (called by the settler)
+///
+/// `remaining_accounts` holds one group per bet: (bet, bettor) in SOL rounds,
+/// (bet, bettor, bettor token account) in token rounds, all writable. Bets
+/// are paid exactly as by `process_payout` until compute runs low; the rest
+/// are left for the next batch. Bets already paid, or already closed, are
+/// skipped, so a batch can safely be resubmitted.
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, ProcessPayoutsBatch<'info>>) -> Result<()> {
+    let round = &mut ctx.accounts.round;
+
+    require!(
+        round.status == RoundStatus::Settling || round.is_refunding(),
+        LeftRightError::RoundNotSettling
+    );
+
+    let group_len = if round.stake_mint.is_some() { 3 } else { 2 };
+    // `usize::is_multiple_of` is newer than the SBF toolchain's rustc
+    #[allow(clippy::manual_is_multiple_of)]
+    let whole_groups = ctx.remaining_accounts.len() % group_len == 0;
+    require!(whole_groups, LeftRightError::InvalidBatchAccounts);
+
+    let stake = Stake::new(
+        round,
+        &ctx.accounts.vault,
+        ctx.bumps.vault,
+        &ctx.accounts.stake_mint,
+        &ctx.accounts.vault_token,
+        &ctx.accounts.token_program,
+        &ctx.accounts.system_program,
+    );
+    let treasury = ctx.accounts.treasury
+        .as_ref()
+        .map(|treasury| treasury.to_account_info());
+    let round_id_bytes = round.round_id.to_le_bytes();
+
+    let mut processed: u32 = 0;
+    let mut skipped: u32 = 0;
+    let mut amount: u64 = 0;
+
+    for accounts in ctx.remaining_accounts.chunks(group_len) {
+        if sol_remaining_compute_units() < COMPUTE_UNITS_PER_PAYOUT {
+            break;
+        }
+
+        let (bet_info, bettor) = (&accounts[0], &accounts[1]);
+
+        // Bets are only closed after being paid
+        if bet_info.data_is_empty() {
+            skipped += 1;
+            continue;
+        }
+
+        // Must be this round's bet PDA for this bettor
+        let mut bet = Account::<Bet>::try_from(bet_info)?;
+        let bet_address = Pubkey::create_program_address(
+            &[
+                Bet::SEED,
+                round_id_bytes.as_ref(),
+                bettor.key.as_ref(),
+                &[bet.bump],
+            ],
+            &crate::ID,
+        )
+        .map_err(|_| LeftRightError::InvalidBatchAccounts)?;
+        require!(
+            bet_address == bet_info.key() && bet.bettor == bettor.key(),
+            LeftRightError::InvalidBatchAccounts
+        );
+
+        if bet.paid_out {
+            skipped += 1;
+            continue;
+        }
+
+        let bettor_token = accounts
+            .get(2)
+            .map(InterfaceAccount::<TokenAccount>::try_from)
+            .transpose()?;
+
+        let payout = pay_bet(
+            round,
+            &mut bet,
+            ctx.accounts.config.refund_fees,
+            &stake,
+            bettor,
+            bettor_token.as_ref(),
+            treasury.clone(),
+            ctx.accounts.treasury_token.as_ref(),
+        )?;
+        bet.exit(&crate::ID)?;
+
+        processed += 1;
+        amount = amount
+            .checked_add(payout)
+            .ok_or(LeftRightError::MathOverflow)?;
+    }
+
+    emit!(PayoutBatchProcessed {
+        round_id: round.round_id,
+        processed,
+        skipped,
+        amount,
+        payouts_remaining: round.bet_count.saturating_sub(round.payouts_processed),
+    });
+
+    Ok(())
+}
@@ -0,0 +1,102 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::LeftRightError;
+use crate::events::PriceSampleRecorded;
+use crate::oracle::{self, pyth};
+use crate::state::{Config, PriceSamples, Round, RoundStatus};
+
+#[derive(Accounts)]
+pub struct RecordPriceSample<'info> {
+    #[account(
+        seeds = [Config::SEED],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        seeds = [Round::SEED, round.round_id.to_le_bytes().as_ref()],
+        bump = round.bump,
+    )]
+    pub round: Account<'info, Round>,
+
+    /// Per-round sample buffer, created by the first cranker
+    #[account(
+        init_if_needed,
+        payer = cranker,
+        space = PriceSamples::LEN,
+        seeds = [PriceSamples::SEED, round.round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub price_samples: Account<'info, PriceSamples>,
+
+    /// Pyth price update account, must be the round's feed
+    /// CHECK: Owner and layout validated by oracle::pyth::read_price
+    #[account(
+        constraint = price_feed.key() == round.price_feed @ LeftRightError::PriceFeedMismatch
+    )]
+    pub price_feed: UncheckedAccount<'info>,
+
+    /// Anyone can crank samples
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Record the round feed's current price as a TWAP sample
+///
+/// Only prices published during the waiting period, from betting close to
+/// round end, are accepted, so the samples a round settles on are fixed once
+/// it ends.
+pub fn handler(ctx: Context<RecordPriceSample>) -> Result<()> {
+    let round = &ctx.accounts.round;
+    let config = &ctx.accounts.config;
+    let clock = Clock::get()?;
+
+    require!(round.twap_samples > 0, LeftRightError::SamplingNotOpen);
+    require!(
+        round.status == RoundStatus::Open || round.status == RoundStatus::Locked,
+        LeftRightError::SamplingNotOpen
+    );
+    require!(
+        clock.unix_timestamp >= round.betting_end_time,
+        LeftRightError::SamplingNotOpen
+    );
+
+    let sample = pyth::read_price(&ctx.accounts.price_feed, &round.price_feed_id)?;
+    oracle::validate_price(&sample, config, clock.unix_timestamp)?;
+    require!(
+        sample.publish_time >= round.betting_end_time,
+        LeftRightError::StalePrice
+    );
+    require!(
+        sample.publish_time <= round.end_time,
+        LeftRightError::SamplingClosed
+    );
+
+    let price_samples = &mut ctx.accounts.price_samples;
+    if price_samples.samples.is_empty() {
+        price_samples.payer = ctx.accounts.cranker.key();
+    }
+    price_samples.round_id = round.round_id;
+    price_samples.bump = ctx.bumps.price_samples;
+
+    // Enforce spacing between consecutive samples
+    if let Some(last) = price_samples.last() {
+        let spacing = sample.publish_time.saturating_sub(last.publish_time);
+        require!(
+            spacing > 0 && spacing >= config.twap_min_spacing_secs,
+            LeftRightError::SampleTooSoon
+        );
+    }
+
+    price_samples.push(sample);
+
+    emit!(PriceSampleRecorded {
+        round_id: round.round_id,
+        price: sample,
+        sample_count: price_samples.samples.len() as u16,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,130 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::errors::LeftRightError;
+use crate::instructions::process_payout::pay_bet;
+use crate::stake::Stake;
+use crate::state::{Bet, Config, Round, RoundStatus};
+
+#[derive(Accounts)]
+pub struct RefundBet<'info> {
+    #[account(
+        seeds = [Config::SEED],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [Round::SEED, round.round_id.to_le_bytes().as_ref()],
+        bump = round.bump,
+    )]
+    pub round: Account<'info, Round>,
+
+    /// Bet being refunded; closed with its rent returned to the bettor
+    /// unless its treasury fee is left owed
+    #[account(
+        mut,
+        seeds = [
+            Bet::SEED,
+            round.round_id.to_le_bytes().as_ref(),
+            bet.bettor.as_ref()
+        ],
+        bump = bet.bump,
+        constraint = bet.round_id == round.round_id,
+    )]
+    pub bet: Account<'info, Bet>,
+
+    /// Round vault PDA holding pool funds
+    /// CHECK: PDA owned by program
+    #[account(
+        mut,
+        seeds = [b"vault", round.round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    /// Bettor receiving the refund
+    /// CHECK: Validated against bet record
+    #[account(
+        mut,
+        constraint = bettor.key() == bet.bettor @ LeftRightError::Unauthorized
+    )]
+    pub bettor: UncheckedAccount<'info>,
+
+    /// Treasury returning the platform fee (only when fee refunds are enabled)
+    #[account(
+        mut,
+        constraint = treasury.key() == config.treasury @ LeftRightError::Unauthorized
+    )]
+    pub treasury: Option<Signer<'info>>,
+
+    /// Stake mint of a token round
+    #[account(
+        constraint = round.stake_mint == Some(stake_mint.key()) @ LeftRightError::InvalidStakeMint
+    )]
+    pub stake_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    /// Token account of a token round the vault PDA holds bets in
+    #[account(
+        mut,
+        seeds = [Round::TOKEN_VAULT_SEED, round.round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub vault_token: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Bettor's token account receiving the refund (token rounds)
+    #[account(mut)]
+    pub bettor_token: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Treasury's token account returning the fee (token rounds)
+    #[account(mut)]
+    pub treasury_token: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Refund a bet of a cancelled round and close it (permissionless)
+///
+/// Paid exactly like a refund from `process_payout` (see `pay_bet`): the pool
+/// contribution comes back from the vault, plus the treasury fee when fee
+/// refunds are enabled and the treasury co-signs. The bet's rent goes back
+/// to the bettor. A bet left with a fee owed stays open until the treasury
+/// settles it (`settle_fee_refund`) and it is closed with `close_bet`.
+pub fn handler(ctx: Context<RefundBet>) -> Result<()> {
+    let round = &mut ctx.accounts.round;
+
+    require!(round.status == RoundStatus::Cancelled, LeftRightError::RoundNotCancelled);
+
+    let stake = Stake::new(
+        round,
+        &ctx.accounts.vault,
+        ctx.bumps.vault,
+        &ctx.accounts.stake_mint,
+        &ctx.accounts.vault_token,
+        &ctx.accounts.token_program,
+        &ctx.accounts.system_program,
+    );
+
+    pay_bet(
+        round,
+        &mut ctx.accounts.bet,
+        ctx.accounts.config.refund_fees,
+        &stake,
+        &ctx.accounts.bettor.to_account_info(),
+        ctx.accounts.bettor_token.as_ref(),
+        ctx.accounts.treasury.as_ref().map(|treasury| treasury.to_account_info()),
+        ctx.accounts.treasury_token.as_ref(),
+    )?;
+
+    if ctx.accounts.bet.fee_owed == 0 {
+        ctx.accounts.bet.close(ctx.accounts.bettor.to_account_info())?;
+        round.bets_closed = round.bets_closed
+            .checked_add(1)
+            .ok_or(LeftRightError::MathOverflow)?;
+    }
+
+    Ok(())
+}
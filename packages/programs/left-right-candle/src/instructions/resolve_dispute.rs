@@ -0,0 +1,179 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions as sysvar_instructions;
+use anchor_lang::system_program;
+
+use crate::errors::LeftRightError;
+use crate::events::{DisputeResolved, RoundSettled};
+use crate::instructions::settle_round::roll_over_pool;
+use crate::oracle::attestation::{self, AttestationKind, PriceAttestation};
+use crate::state::{Config, Price, Round, RoundStatus};
+
+#[derive(Accounts)]
+pub struct ResolveDispute<'info> {
+    #[account(
+        mut,
+        seeds = [Config::SEED],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [Round::SEED, round.round_id.to_le_bytes().as_ref()],
+        bump = round.bump,
+    )]
+    pub round: Account<'info, Round>,
+
+    /// Bond vault PDA holding settlement bonds for this round
+    /// CHECK: PDA owned by program
+    #[account(
+        mut,
+        seeds = [Round::BOND_SEED, round.round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub bond_vault: SystemAccount<'info>,
+
+    /// CHECK: Validated against round record
+    #[account(
+        mut,
+        constraint = proposer.key() == round.proposer @ LeftRightError::Unauthorized
+    )]
+    pub proposer: UncheckedAccount<'info>,
+
+    /// CHECK: Validated against round record
+    #[account(
+        mut,
+        constraint = Some(disputer.key()) == round.disputer @ LeftRightError::Unauthorized
+    )]
+    pub disputer: UncheckedAccount<'info>,
+
+    /// Round vault PDA holding the pool
+    /// CHECK: PDA owned by program
+    #[account(
+        mut,
+        seeds = [b"vault", round.round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    /// Vault of the next round to start, receives the pool of a rolled-over tie
+    /// CHECK: PDA owned by program
+    #[account(
+        mut,
+        seeds = [b"vault", config.round_counter.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub next_vault: SystemAccount<'info>,
+
+    /// Instructions sysvar, used to verify price attestations
+    /// CHECK: Address checked against the sysvar id
+    #[account(address = sysvar_instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+
+    /// Admin, or anyone submitting an end price attested by the signer quorum
+    pub resolver: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Resolve a disputed settlement with the correct end price
+///
+/// Whichever of proposer and disputer matched the resolved outcome gets both
+/// bonds back; the other party's bond is slashed to them.
+pub fn handler(ctx: Context<ResolveDispute>, end_price: Price) -> Result<()> {
+    let round = &mut ctx.accounts.round;
+
+    require!(round.status == RoundStatus::Disputed, LeftRightError::RoundNotDisputed);
+
+    // Admin resolves directly; anyone else needs the signer quorum
+    if ctx.accounts.resolver.key() != ctx.accounts.config.admin {
+        attestation::verify_attestation(
+            &ctx.accounts.instructions_sysvar,
+            &ctx.accounts.config,
+            &PriceAttestation {
+                round_id: round.round_id,
+                kind: AttestationKind::End,
+                asset_symbol: round.asset_symbol.clone(),
+                price: end_price,
+            },
+        )?;
+    }
+
+    require!(end_price.price > 0, LeftRightError::InvalidPriceFeed);
+    require!(
+        end_price.publish_time >= round.end_time,
+        LeftRightError::StalePrice
+    );
+
+    let proposed_side = round.winning_side;
+    let winning_side = round.winning_side_for(&end_price)?;
+
+    let bond_winner = if winning_side == proposed_side {
+        ctx.accounts.proposer.to_account_info()
+    } else {
+        ctx.accounts.disputer.to_account_info()
+    };
+
+    // Winner takes both bonds
+    let payout = round.settlement_bond
+        .checked_mul(2)
+        .ok_or(LeftRightError::MathOverflow)?;
+
+    let round_id_bytes = round.round_id.to_le_bytes();
+    let bond_seeds = &[
+        Round::BOND_SEED,
+        round_id_bytes.as_ref(),
+        &[ctx.bumps.bond_vault],
+    ];
+
+    system_program::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.bond_vault.to_account_info(),
+                to: bond_winner.clone(),
+            },
+            &[bond_seeds],
+        ),
+        payout,
+    )?;
+
+    round.end_price = end_price;
+    round.winning_side = winning_side;
+
+    let rollover_amount = round.rollover_amount();
+    roll_over_pool(
+        round,
+        &mut ctx.accounts.config,
+        rollover_amount,
+        ctx.accounts.vault.to_account_info(),
+        ctx.bumps.vault,
+        ctx.accounts.next_vault.to_account_info(),
+        ctx.accounts.system_program.to_account_info(),
+    )?;
+
+    round.status = round.payout_status();
+
+    emit!(DisputeResolved {
+        round_id: round.round_id,
+        resolver: ctx.accounts.resolver.key(),
+        end_price,
+        winning_side: round.outcome(),
+        bond_winner: bond_winner.key(),
+        slashed_bond: round.settlement_bond,
+    });
+
+    emit!(RoundSettled {
+        round_id: round.round_id,
+        settler: ctx.accounts.resolver.key(),
+        price_sources: round.price_sources.clone(),
+        start_price: round.start_price,
+        end_price,
+        end_readings: round.end_readings,
+        winning_side: round.outcome(),
+        total_pool: round.total_pool(),
+        winning_pool: round.winning_pool(),
+    });
+
+    Ok(())
+}
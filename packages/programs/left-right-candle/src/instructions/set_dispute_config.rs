@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+use crate::errors::LeftRightError;
+use crate::events::DisputeConfigUpdated;
+use crate::state::Config;
+
+#[derive(Accounts)]
+pub struct SetDisputeConfig<'info> {
+    #[account(
+        mut,
+        seeds = [Config::SEED],
+        bump = config.bump,
+        has_one = admin @ LeftRightError::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+
+    pub admin: Signer<'info>,
+}
+
+/// Configure optimistic settlement. A zero window disables it: settlement
+/// is final immediately and no bond is taken. Otherwise the bond must be
+/// enough to leave the empty bond vault rent-exempt.
+pub fn handler(
+    ctx: Context<SetDisputeConfig>,
+    dispute_window_secs: i64,
+    settlement_bond_lamports: u64,
+) -> Result<()> {
+    require!(dispute_window_secs >= 0, LeftRightError::InvalidDisputeConfig);
+    require!(
+        dispute_window_secs == 0
+            || settlement_bond_lamports >= Rent::get()?.minimum_balance(0),
+        LeftRightError::InvalidDisputeConfig
+    );
+
+    let config = &mut ctx.accounts.config;
+    config.dispute_window_secs = dispute_window_secs;
+    config.settlement_bond_lamports = settlement_bond_lamports;
+
+    emit!(DisputeConfigUpdated {
+        dispute_window_secs,
+        settlement_bond_lamports,
+    });
+
+    Ok(())
+}
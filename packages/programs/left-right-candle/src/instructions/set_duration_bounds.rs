@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+use crate::errors::LeftRightError;
+use crate::events::DurationBoundsUpdated;
+use crate::state::Config;
+
+#[derive(Accounts)]
+pub struct SetDurationBounds<'info> {
+    #[account(
+        mut,
+        seeds = [Config::SEED],
+        bump = config.bump,
+        has_one = admin @ LeftRightError::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+
+    pub admin: Signer<'info>,
+}
+
+/// Set the range of betting and waiting durations `start_round` accepts
+pub fn handler(
+    ctx: Context<SetDurationBounds>,
+    min_betting_secs: i64,
+    max_betting_secs: i64,
+    min_waiting_secs: i64,
+    max_waiting_secs: i64,
+) -> Result<()> {
+    // Weight curves are resolved over the window, so it needs some length
+    require!(
+        min_betting_secs >= 4 && min_betting_secs <= max_betting_secs,
+        LeftRightError::InvalidRoundDuration
+    );
+    require!(
+        min_waiting_secs >= 0 && min_waiting_secs <= max_waiting_secs,
+        LeftRightError::InvalidRoundDuration
+    );
+
+    let config = &mut ctx.accounts.config;
+    config.min_betting_secs = min_betting_secs;
+    config.max_betting_secs = max_betting_secs;
+    config.min_waiting_secs = min_waiting_secs;
+    config.max_waiting_secs = max_waiting_secs;
+
+    emit!(DurationBoundsUpdated {
+        min_betting_secs,
+        max_betting_secs,
+        min_waiting_secs,
+        max_waiting_secs,
+    });
+
+    Ok(())
+}
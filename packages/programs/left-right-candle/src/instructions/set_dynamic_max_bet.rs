@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+use crate::errors::LeftRightError;
+use crate::events::DynamicMaxBetUpdated;
+use crate::state::Config;
+
+#[derive(Accounts)]
+pub struct SetDynamicMaxBet<'info> {
+    #[account(
+        mut,
+        seeds = [Config::SEED],
+        bump = config.bump,
+        has_one = admin @ LeftRightError::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+
+    pub admin: Signer<'info>,
+}
+
+/// Limit each bet to max(floor, bps of the round's current pool). Zero bps
+/// disables the rule; `max_bet_lamports` applies either way.
+pub fn handler(
+    ctx: Context<SetDynamicMaxBet>,
+    dynamic_max_bet_bps: u16,
+    dynamic_max_bet_floor_lamports: u64,
+) -> Result<()> {
+    require!(dynamic_max_bet_bps <= 10000, LeftRightError::InvalidDynamicMaxBetBps);
+    // A zero floor would block the first bet into an empty pool
+    require!(
+        dynamic_max_bet_bps == 0 || dynamic_max_bet_floor_lamports >= ctx.accounts.config.min_bet_lamports,
+        LeftRightError::InvalidBetLimits
+    );
+
+    let config = &mut ctx.accounts.config;
+    config.dynamic_max_bet_bps = dynamic_max_bet_bps;
+    config.dynamic_max_bet_floor_lamports = dynamic_max_bet_floor_lamports;
+
+    emit!(DynamicMaxBetUpdated {
+        dynamic_max_bet_bps,
+        dynamic_max_bet_floor_lamports,
+    });
+
+    Ok(())
+}
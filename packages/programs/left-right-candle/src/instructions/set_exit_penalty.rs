@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+use crate::errors::LeftRightError;
+use crate::events::ExitPenaltyUpdated;
+use crate::state::Config;
+
+#[derive(Accounts)]
+pub struct SetExitPenalty<'info> {
+    #[account(
+        mut,
+        seeds = [Config::SEED],
+        bump = config.bump,
+        has_one = admin @ LeftRightError::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+
+    pub admin: Signer<'info>,
+}
+
+/// Set the penalty on early withdrawals and where it goes (treasury, or the
+/// round's winners)
+pub fn handler(
+    ctx: Context<SetExitPenalty>,
+    exit_penalty_bps: u16,
+    exit_penalty_to_treasury: bool,
+) -> Result<()> {
+    require!(exit_penalty_bps <= 10000, LeftRightError::InvalidExitPenaltyBps);
+
+    let config = &mut ctx.accounts.config;
+    config.exit_penalty_bps = exit_penalty_bps;
+    config.exit_penalty_to_treasury = exit_penalty_to_treasury;
+
+    emit!(ExitPenaltyUpdated {
+        exit_penalty_bps,
+        exit_penalty_to_treasury,
+    });
+
+    Ok(())
+}
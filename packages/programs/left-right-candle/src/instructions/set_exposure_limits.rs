@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+use crate::errors::LeftRightError;
+use crate::events::ExposureLimitsUpdated;
+use crate::state::Config;
+
+#[derive(Accounts)]
+pub struct SetExposureLimits<'info> {
+    #[account(
+        mut,
+        seeds = [Config::SEED],
+        bump = config.bump,
+        has_one = admin @ LeftRightError::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+
+    pub admin: Signer<'info>,
+}
+
+/// Set per-wallet betting caps of SOL rounds, before fees. Zero disables a
+/// cap. Token rounds are capped by their mint's `MintConfig`.
+pub fn handler(
+    ctx: Context<SetExposureLimits>,
+    max_round_exposure_lamports: u64,
+    max_daily_exposure_lamports: u64,
+) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    config.max_round_exposure_lamports = max_round_exposure_lamports;
+    config.max_daily_exposure_lamports = max_daily_exposure_lamports;
+
+    emit!(ExposureLimitsUpdated {
+        max_round_exposure_lamports,
+        max_daily_exposure_lamports,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,73 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+use crate::errors::LeftRightError;
+use crate::events::MintConfigUpdated;
+use crate::stake;
+use crate::state::{Config, MintConfig};
+
+#[derive(Accounts)]
+pub struct SetMintConfig<'info> {
+    #[account(
+        seeds = [Config::SEED],
+        bump = config.bump,
+        has_one = admin @ LeftRightError::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Limits for the mint, created the first time it is configured
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = MintConfig::LEN,
+        seeds = [MintConfig::SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub mint_config: Account<'info, MintConfig>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Enable a stake mint for token rounds, or update its bet limits.
+/// Amounts are in the mint's base units. Token and Token-2022 mints are
+/// accepted, except Token-2022 mints with extensions that could move or
+/// lock the vault's tokens.
+pub fn handler(
+    ctx: Context<SetMintConfig>,
+    min_bet: u64,
+    max_bet: u64,
+    max_round_exposure: u64,
+    max_daily_exposure: u64,
+    dynamic_max_bet_floor: u64,
+) -> Result<()> {
+    stake::check_mint_extensions(&ctx.accounts.mint.to_account_info())?;
+
+    require!(min_bet > 0 && min_bet < max_bet, LeftRightError::InvalidBetLimits);
+    // A floor below the minimum bet would block the first bet into an empty
+    // pool under the dynamic rule
+    require!(dynamic_max_bet_floor >= min_bet, LeftRightError::InvalidBetLimits);
+
+    let mint_config = &mut ctx.accounts.mint_config;
+    mint_config.mint = ctx.accounts.mint.key();
+    mint_config.min_bet = min_bet;
+    mint_config.max_bet = max_bet;
+    mint_config.max_round_exposure = max_round_exposure;
+    mint_config.max_daily_exposure = max_daily_exposure;
+    mint_config.dynamic_max_bet_floor = dynamic_max_bet_floor;
+    mint_config.bump = ctx.bumps.mint_config;
+
+    emit!(MintConfigUpdated {
+        mint: mint_config.mint,
+        min_bet,
+        max_bet,
+        max_round_exposure,
+        max_daily_exposure,
+        dynamic_max_bet_floor,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+use crate::errors::LeftRightError;
+use crate::events::OracleConfigUpdated;
+use crate::state::Config;
+
+#[derive(Accounts)]
+pub struct SetOracleConfig<'info> {
+    #[account(
+        mut,
+        seeds = [Config::SEED],
+        bump = config.bump,
+        has_one = admin @ LeftRightError::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(
+    ctx: Context<SetOracleConfig>,
+    max_price_age_secs: i64,
+    max_conf_bps: u16,
+) -> Result<()> {
+    require!(max_price_age_secs > 0, LeftRightError::InvalidOracleConfig);
+    require!(max_conf_bps <= 10000, LeftRightError::InvalidOracleConfig);
+
+    let config = &mut ctx.accounts.config;
+    config.max_price_age_secs = max_price_age_secs;
+    config.max_conf_bps = max_conf_bps;
+
+    emit!(OracleConfigUpdated {
+        max_price_age_secs,
+        max_conf_bps,
+    });
+
+    Ok(())
+}
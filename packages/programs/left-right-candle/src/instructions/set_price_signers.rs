@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+use crate::errors::LeftRightError;
+use crate::events::PriceSignersUpdated;
+use crate::state::Config;
+
+#[derive(Accounts)]
+pub struct SetPriceSigners<'info> {
+    #[account(
+        mut,
+        seeds = [Config::SEED],
+        bump = config.bump,
+        has_one = admin @ LeftRightError::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+
+    pub admin: Signer<'info>,
+}
+
+/// Replace the price signer set. An empty set with threshold 0 disables
+/// attestation-based pricing.
+pub fn handler(
+    ctx: Context<SetPriceSigners>,
+    price_signers: Vec<Pubkey>,
+    signer_threshold: u8,
+) -> Result<()> {
+    require!(
+        price_signers.len() <= Config::MAX_PRICE_SIGNERS,
+        LeftRightError::InvalidSignerSet
+    );
+    require!(
+        (signer_threshold as usize) <= price_signers.len(),
+        LeftRightError::InvalidSignerSet
+    );
+    require!(
+        price_signers.is_empty() || signer_threshold > 0,
+        LeftRightError::InvalidSignerSet
+    );
+    for (i, signer) in price_signers.iter().enumerate() {
+        require!(
+            !price_signers[..i].contains(signer),
+            LeftRightError::InvalidSignerSet
+        );
+    }
+
+    let config = &mut ctx.accounts.config;
+    config.price_signers = price_signers.clone();
+    config.signer_threshold = signer_threshold;
+
+    emit!(PriceSignersUpdated {
+        price_signers,
+        signer_threshold,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+use crate::errors::LeftRightError;
+use crate::events::RefundFeesUpdated;
+use crate::state::Config;
+
+#[derive(Accounts)]
+pub struct SetRefundFees<'info> {
+    #[account(
+        mut,
+        seeds = [Config::SEED],
+        bump = config.bump,
+        has_one = admin @ LeftRightError::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+
+    pub admin: Signer<'info>,
+}
+
+/// Choose whether refunds also return the treasury fee. When enabled, the fee
+/// is returned on refund payouts the treasury co-signs, and recorded as owed
+/// on the bet for the rest (see `settle_fee_refund`).
+pub fn handler(ctx: Context<SetRefundFees>, refund_fees: bool) -> Result<()> {
+    ctx.accounts.config.refund_fees = refund_fees;
+
+    emit!(RefundFeesUpdated { refund_fees });
+
+    Ok(())
+}
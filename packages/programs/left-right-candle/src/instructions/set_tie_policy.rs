@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+use crate::errors::LeftRightError;
+use crate::events::TiePolicyUpdated;
+use crate::state::{Config, TiePolicy};
+
+#[derive(Accounts)]
+pub struct SetTiePolicy<'info> {
+    #[account(
+        mut,
+        seeds = [Config::SEED],
+        bump = config.bump,
+        has_one = admin @ LeftRightError::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+
+    pub admin: Signer<'info>,
+}
+
+/// Set the tie rule. Rounds keep the policy they started with.
+pub fn handler(ctx: Context<SetTiePolicy>, tie_policy: TiePolicy) -> Result<()> {
+    ctx.accounts.config.tie_policy = tie_policy;
+
+    emit!(TiePolicyUpdated { tie_policy });
+
+    Ok(())
+}
@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+use crate::errors::LeftRightError;
+use crate::events::TwapConfigUpdated;
+use crate::state::{Config, PriceSamples};
+
+#[derive(Accounts)]
+pub struct SetTwapConfig<'info> {
+    #[account(
+        mut,
+        seeds = [Config::SEED],
+        bump = config.bump,
+        has_one = admin @ LeftRightError::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(
+    ctx: Context<SetTwapConfig>,
+    twap_min_samples: u8,
+    twap_min_spacing_secs: i64,
+) -> Result<()> {
+    require!(
+        twap_min_samples >= 2 && (twap_min_samples as usize) <= PriceSamples::MAX_SAMPLES,
+        LeftRightError::InvalidTwapConfig
+    );
+    require!(twap_min_spacing_secs >= 0, LeftRightError::InvalidTwapConfig);
+
+    let config = &mut ctx.accounts.config;
+    config.twap_min_samples = twap_min_samples;
+    config.twap_min_spacing_secs = twap_min_spacing_secs;
+
+    emit!(TwapConfigUpdated {
+        twap_min_samples,
+        twap_min_spacing_secs,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+use crate::errors::LeftRightError;
+use crate::events::VoidGracePeriodUpdated;
+use crate::state::Config;
+
+#[derive(Accounts)]
+pub struct SetVoidGracePeriod<'info> {
+    #[account(
+        mut,
+        seeds = [Config::SEED],
+        bump = config.bump,
+        has_one = admin @ LeftRightError::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+
+    pub admin: Signer<'info>,
+}
+
+/// Set how long after `end_time` a round must stay unsettled before anyone
+/// can void it
+pub fn handler(ctx: Context<SetVoidGracePeriod>, void_grace_secs: i64) -> Result<()> {
+    require!(void_grace_secs > 0, LeftRightError::InvalidVoidGracePeriod);
+
+    ctx.accounts.config.void_grace_secs = void_grace_secs;
+
+    emit!(VoidGracePeriodUpdated { void_grace_secs });
+
+    Ok(())
+}
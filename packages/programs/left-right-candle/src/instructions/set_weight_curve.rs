@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+use crate::errors::LeftRightError;
+use crate::events::WeightCurveUpdated;
+use crate::state::{Config, WeightCurve};
+
+#[derive(Accounts)]
+pub struct SetWeightCurve<'info> {
+    #[account(
+        mut,
+        seeds = [Config::SEED],
+        bump = config.bump,
+        has_one = admin @ LeftRightError::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+
+    pub admin: Signer<'info>,
+}
+
+/// Set the early-bird weight curve. Rounds keep the curve they started with.
+pub fn handler(ctx: Context<SetWeightCurve>, weight_curve: WeightCurve) -> Result<()> {
+    weight_curve.validate()?;
+
+    ctx.accounts.config.weight_curve = weight_curve.clone();
+
+    emit!(WeightCurveUpdated { weight_curve });
+
+    Ok(())
+}
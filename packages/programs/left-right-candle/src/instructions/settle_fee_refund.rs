@@ -0,0 +1,117 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::errors::LeftRightError;
+use crate::events::FeeRefundSettled;
+use crate::stake::Stake;
+use crate::state::{Bet, Config, Round};
+
+#[derive(Accounts)]
+pub struct SettleFeeRefund<'info> {
+    #[account(
+        seeds = [Config::SEED],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        seeds = [Round::SEED, round.round_id.to_le_bytes().as_ref()],
+        bump = round.bump,
+    )]
+    pub round: Account<'info, Round>,
+
+    #[account(
+        mut,
+        seeds = [
+            Bet::SEED,
+            round.round_id.to_le_bytes().as_ref(),
+            bet.bettor.as_ref()
+        ],
+        bump = bet.bump,
+        constraint = bet.round_id == round.round_id,
+    )]
+    pub bet: Account<'info, Bet>,
+
+    /// Round vault PDA (not touched: the fee comes from the treasury)
+    /// CHECK: PDA owned by program
+    #[account(
+        seeds = [b"vault", round.round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    /// Bettor receiving the owed fee
+    /// CHECK: Validated against bet record
+    #[account(
+        mut,
+        constraint = bettor.key() == bet.bettor @ LeftRightError::Unauthorized
+    )]
+    pub bettor: UncheckedAccount<'info>,
+
+    /// Treasury paying back the fee
+    #[account(
+        mut,
+        constraint = treasury.key() == config.treasury @ LeftRightError::Unauthorized
+    )]
+    pub treasury: Signer<'info>,
+
+    /// Stake mint of a token round
+    #[account(
+        constraint = round.stake_mint == Some(stake_mint.key()) @ LeftRightError::InvalidStakeMint
+    )]
+    pub stake_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    /// Token account of a token round the vault PDA holds bets in
+    #[account(
+        seeds = [Round::TOKEN_VAULT_SEED, round.round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub vault_token: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Bettor's token account receiving the fee (token rounds)
+    #[account(mut)]
+    pub bettor_token: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Treasury's token account paying the fee (token rounds)
+    #[account(mut)]
+    pub treasury_token: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Pay back a treasury fee a refund left owed (see `Bet::fee_owed`), signed
+/// by the treasury. The bet can then be closed with `close_bet`.
+pub fn handler(ctx: Context<SettleFeeRefund>) -> Result<()> {
+    let bet = &mut ctx.accounts.bet;
+    let amount = bet.fee_owed;
+    require!(amount > 0, LeftRightError::NoFeeRefundOwed);
+
+    let stake = Stake::new(
+        &ctx.accounts.round,
+        &ctx.accounts.vault,
+        ctx.bumps.vault,
+        &ctx.accounts.stake_mint,
+        &ctx.accounts.vault_token,
+        &ctx.accounts.token_program,
+        &ctx.accounts.system_program,
+    );
+    stake.transfer(
+        &ctx.accounts.treasury.to_account_info(),
+        ctx.accounts.treasury_token.as_ref(),
+        &ctx.accounts.bettor.to_account_info(),
+        ctx.accounts.bettor_token.as_ref(),
+        amount,
+    )?;
+
+    bet.fee_owed = 0;
+
+    emit!(FeeRefundSettled {
+        round_id: bet.round_id,
+        bettor: bet.bettor,
+        amount,
+    });
+
+    Ok(())
+}
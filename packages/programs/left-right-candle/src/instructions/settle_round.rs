@@ -1,12 +1,17 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions as sysvar_instructions;
+use anchor_lang::system_program;
 
 use crate::errors::LeftRightError;
-use crate::events::RoundSettled;
-use crate::state::{Config, Round, RoundStatus, Side};
+use crate::events::{PoolRolledOver, RoundNoContest, RoundSettled, SettlementProposed};
+use crate::oracle::attestation::{self, AttestationKind, PriceAttestation};
+use crate::oracle::{self, pyth, switchboard, PriceReadings};
+use crate::state::{Config, Price, PriceSamples, PriceSource, Round, RoundStatus};
 
 #[derive(Accounts)]
 pub struct SettleRound<'info> {
     #[account(
+        mut,
         seeds = [Config::SEED],
         bump = config.bump,
     )]
@@ -19,16 +24,91 @@ pub struct SettleRound<'info> {
     )]
     pub round: Account<'info, Round>,
 
-    /// Admin who can settle rounds
+    /// Pyth price update account, must be the feed the round started with
+    /// (if Pyth is a source)
+    /// CHECK: Owner and layout validated by oracle::pyth::read_price
+    #[account(
+        constraint = price_feed.key() == round.price_feed @ LeftRightError::PriceFeedMismatch
+    )]
+    pub price_feed: Option<UncheckedAccount<'info>>,
+
+    /// Switchboard aggregator, must be the one the round started with
+    /// (Switchboard rounds only)
+    /// CHECK: Owner and layout validated by oracle::switchboard::read_price
+    #[account(
+        constraint = switchboard_feed.key() == round.switchboard_feed @ LeftRightError::PriceFeedMismatch
+    )]
+    pub switchboard_feed: Option<UncheckedAccount<'info>>,
+
+    /// Recorded price samples (TWAP rounds only)
+    #[account(
+        seeds = [PriceSamples::SEED, round.round_id.to_le_bytes().as_ref()],
+        bump = price_samples.bump,
+    )]
+    pub price_samples: Option<Account<'info, PriceSamples>>,
+
+    /// Instructions sysvar, used to verify price attestations
+    /// CHECK: Address checked against the sysvar id
+    #[account(address = sysvar_instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+
+    /// Bond vault PDA holding settlement bonds for this round
+    /// CHECK: PDA owned by program
+    #[account(
+        mut,
+        seeds = [Round::BOND_SEED, round.round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub bond_vault: SystemAccount<'info>,
+
+    /// Round vault PDA holding the pool
+    /// CHECK: PDA owned by program
     #[account(
-        constraint = admin.key() == config.admin @ LeftRightError::Unauthorized
+        mut,
+        seeds = [b"vault", round.round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    /// Vault of the next round to start, receives the pool of a rolled-over tie
+    /// CHECK: PDA owned by program
+    #[account(
+        mut,
+        seeds = [b"vault", config.round_counter.to_le_bytes().as_ref()],
+        bump
     )]
-    pub admin: Signer<'info>,
+    pub next_vault: SystemAccount<'info>,
+
+    /// Anyone can settle: the price comes from the round's price source,
+    /// never from the caller's authority. Posts the settlement bond when
+    /// optimistic settlement is enabled.
+    #[account(mut)]
+    pub settler: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
 }
 
-/// Settle round with admin-provided end price (fetched from CoinGecko/other API)
+/// Settle round with the median end price of the round's price sources
+///
+/// Every configured source contributes one reading; at least
+/// `round.oracle_quorum` of them must be fresh. On TWAP rounds the Pyth
+/// reading is the time-weighted average of the last `round.twap_samples`
+/// samples recorded by `record_price_sample`, all published during the
+/// waiting period; it is checked for freshness against the round end rather
+/// than the settlement time.
+///
+/// With a dispute window configured the end price is only proposed: the
+/// settler posts a bond and the round moves to `Proposed` until the window
+/// passes (`finalize_settlement`) or someone disputes it.
+///
+/// When the end price equals the start price the round's tie policy decides:
+/// one side wins, everyone is refunded, or the pool rolls into the next round.
+///
+/// A round with no bets on one side has no contest: no price is read and
+/// the round goes straight to `Refunding`.
+///
 /// Note: Fees (treasury 2.5% + referrer 1%) were already collected at bet time
-pub fn handler(ctx: Context<SettleRound>, end_price: i64) -> Result<()> {
+pub fn handler(ctx: Context<SettleRound>, attested_price: Option<Price>) -> Result<()> {
     let round = &mut ctx.accounts.round;
     let clock = Clock::get()?;
 
@@ -42,36 +122,233 @@ pub fn handler(ctx: Context<SettleRound>, end_price: i64) -> Result<()> {
         LeftRightError::RoundNotEnded
     );
 
-    // Set end price (provided by admin from off-chain oracle like CoinGecko)
+    if round.is_one_sided() {
+        return settle_no_contest(ctx);
+    }
+
+    require!(
+        attested_price.is_none() || round.has_price_source(PriceSource::Attestation),
+        LeftRightError::InvalidPriceSource
+    );
+
+    // Collect one reading per configured source; stale or wide readings
+    // are dropped, and the price must have been published after round end
+    let config = &ctx.accounts.config;
+    let mut readings = PriceReadings::default();
+    for source in round.price_sources.iter().copied() {
+        let reading = match source {
+            PriceSource::Pyth if round.twap_samples > 0 => {
+                let price_samples = ctx.accounts.price_samples
+                    .as_ref()
+                    .ok_or(LeftRightError::NotEnoughSamples)?;
+                Some(price_samples.twap(round.twap_samples as usize)?)
+            }
+            PriceSource::Pyth => {
+                let price_feed = ctx.accounts.price_feed
+                    .as_ref()
+                    .ok_or(LeftRightError::InvalidPriceSource)?;
+                Some(pyth::read_price(price_feed, &round.price_feed_id)?)
+            }
+            PriceSource::Switchboard => {
+                let switchboard_feed = ctx.accounts.switchboard_feed
+                    .as_ref()
+                    .ok_or(LeftRightError::InvalidPriceSource)?;
+                Some(switchboard::read_price(switchboard_feed)?)
+            }
+            // Attestations are optional: signers may be offline
+            PriceSource::Attestation => match attested_price {
+                Some(price) => {
+                    attestation::verify_attestation(
+                        &ctx.accounts.instructions_sysvar,
+                        config,
+                        &PriceAttestation {
+                            round_id: round.round_id,
+                            kind: AttestationKind::End,
+                            asset_symbol: round.asset_symbol.clone(),
+                            price,
+                        },
+                    )?;
+                    Some(price)
+                }
+                None => None,
+            },
+        };
+
+        // A TWAP closes with the round's last sample, at or before its end
+        let (current_time, not_before) = match source {
+            PriceSource::Pyth if round.twap_samples > 0 => {
+                (round.end_time, round.betting_end_time)
+            }
+            _ => (clock.unix_timestamp, round.end_time),
+        };
+
+        if let Some(price) = reading {
+            oracle::record_reading(
+                &mut readings,
+                source,
+                price,
+                config,
+                current_time,
+                not_before,
+            );
+        }
+    }
+
+    let end_price = oracle::median_price(&readings, round.oracle_quorum)?;
+    round.end_readings = readings;
     round.end_price = end_price;
 
-    // Determine winning side
-    let winning_side = if end_price < round.start_price {
-        Side::Left
-    } else {
-        Side::Right  // Ties go to RIGHT
-    };
-    round.winning_side = Some(winning_side);
+    // Determine winning side (exponent-aware comparison, ties per tie policy)
+    round.winning_side = round.winning_side_for(&end_price)?;
 
-    let total_pool = round.total_pool();
-    let winning_pool = match winning_side {
-        Side::Left => round.left_pool,
-        Side::Right => round.right_pool,
-    };
+    if config.dispute_window_secs > 0 {
+        // Optimistic settlement: post bond and open the challenge window
+        let bond = config.settlement_bond_lamports;
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.settler.to_account_info(),
+                    to: ctx.accounts.bond_vault.to_account_info(),
+                },
+            ),
+            bond,
+        )?;
+
+        round.proposer = ctx.accounts.settler.key();
+        round.challenge_end_time = clock.unix_timestamp
+            .checked_add(config.dispute_window_secs)
+            .ok_or(LeftRightError::MathOverflow)?;
+        round.settlement_bond = bond;
+        round.status = RoundStatus::Proposed;
+
+        emit!(SettlementProposed {
+            round_id: round.round_id,
+            proposer: round.proposer,
+            end_price,
+            winning_side: round.outcome(),
+            bond,
+            challenge_end_time: round.challenge_end_time,
+        });
+
+        return Ok(());
+    }
 
     // No fee collection here - fees were taken upfront at bet time
     // The vault contains only the pool amounts (after fees)
 
-    // Update round status
-    round.status = RoundStatus::Settling;
+    let rollover_amount = round.rollover_amount();
+    roll_over_pool(
+        round,
+        &mut ctx.accounts.config,
+        rollover_amount,
+        ctx.accounts.vault.to_account_info(),
+        ctx.bumps.vault,
+        ctx.accounts.next_vault.to_account_info(),
+        ctx.accounts.system_program.to_account_info(),
+    )?;
+
+    // Update round status (refunded ties pay back every bet)
+    round.status = round.payout_status();
 
     emit!(RoundSettled {
         round_id: round.round_id,
+        settler: ctx.accounts.settler.key(),
+        price_sources: round.price_sources.clone(),
         start_price: round.start_price,
         end_price,
-        winning_side: winning_side as u8,
-        total_pool,
-        winning_pool,
+        end_readings: readings,
+        winning_side: round.outcome(),
+        total_pool: round.total_pool(),
+        winning_pool: round.winning_pool(),
+    });
+
+    Ok(())
+}
+
+/// Refund every bet of a one-sided round. Its bonus pool, if any, moves on
+/// to the next round.
+fn settle_no_contest(ctx: Context<SettleRound>) -> Result<()> {
+    let round = &mut ctx.accounts.round;
+
+    let bonus_pool = round.bonus_pool;
+    roll_over_pool(
+        round,
+        &mut ctx.accounts.config,
+        bonus_pool,
+        ctx.accounts.vault.to_account_info(),
+        ctx.bumps.vault,
+        ctx.accounts.next_vault.to_account_info(),
+        ctx.accounts.system_program.to_account_info(),
+    )?;
+
+    round.winning_side = None;
+    round.status = if round.bet_count == 0 {
+        RoundStatus::Settled
+    } else {
+        RoundStatus::Refunding
+    };
+
+    emit!(RoundNoContest {
+        round_id: round.round_id,
+        left_pool: round.left_pool,
+        right_pool: round.right_pool,
+        bet_count: round.bet_count,
+    });
+
+    Ok(())
+}
+
+/// Move funds a settled round leaves unclaimed (see `Round::rollover_amount`)
+/// into the vault of the next round to start, which picks them up as its
+/// bonus pool. Token rounds keep them in their own vault instead, for the
+/// treasury sweep, since the next round may not share their stake mint. So
+/// does an amount too small to leave an empty next vault rent-exempt.
+pub(crate) fn roll_over_pool<'info>(
+    round: &Round,
+    config: &mut Config,
+    amount: u64,
+    vault: AccountInfo<'info>,
+    vault_bump: u8,
+    next_vault: AccountInfo<'info>,
+    system_program: AccountInfo<'info>,
+) -> Result<()> {
+    if amount == 0 || round.stake_mint.is_some() {
+        return Ok(());
+    }
+
+    let rent_exempt_minimum = Rent::get()?.minimum_balance(0);
+    if next_vault.lamports().saturating_add(amount) < rent_exempt_minimum {
+        return Ok(());
+    }
+
+    let round_id_bytes = round.round_id.to_le_bytes();
+    let vault_seeds = &[
+        b"vault".as_ref(),
+        round_id_bytes.as_ref(),
+        &[vault_bump],
+    ];
+
+    system_program::transfer(
+        CpiContext::new_with_signer(
+            system_program,
+            system_program::Transfer {
+                from: vault,
+                to: next_vault,
+            },
+            &[vault_seeds],
+        ),
+        amount,
+    )?;
+
+    config.rollover_pool = config.rollover_pool
+        .checked_add(amount)
+        .ok_or(LeftRightError::MathOverflow)?;
+
+    emit!(PoolRolledOver {
+        round_id: round.round_id,
+        next_round_id: config.round_counter,
+        amount,
     });
 
     Ok(())
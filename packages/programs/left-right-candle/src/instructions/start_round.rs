@@ -1,8 +1,15 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions as sysvar_instructions;
+use anchor_lang::system_program;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 use crate::errors::LeftRightError;
 use crate::events::RoundStarted;
-use crate::state::{Config, Round, RoundStatus};
+use crate::oracle::attestation::{self, AttestationKind, PriceAttestation};
+use crate::oracle::{self, pyth, switchboard, PriceReadings, MAX_PRICE_SOURCES};
+use crate::state::{
+    Config, MintConfig, Price, PriceSamples, PriceSource, Round, RoundStatus, TiePolicy,
+};
 
 #[derive(Accounts)]
 #[instruction(asset_symbol: String)]
@@ -24,30 +31,205 @@ pub struct StartRound<'info> {
     )]
     pub round: Account<'info, Round>,
 
-    /// Vault PDA to hold bets for this round
+    /// Vault PDA to hold bets for this round, funded with a rent-exempt
+    /// reserve by the admin in a SOL round
     /// CHECK: PDA owned by program
     #[account(
+        mut,
         seeds = [b"vault", config.round_counter.to_le_bytes().as_ref()],
         bump
     )]
     pub vault: SystemAccount<'info>,
 
+    /// Mint bets are staked in, for a token round (omit for a SOL round)
+    pub stake_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    /// Bet limits of the stake mint; its presence enables the mint
+    #[account(
+        constraint = stake_mint.as_ref().map(|mint| mint.key()) == Some(mint_config.mint)
+            @ LeftRightError::InvalidStakeMint
+    )]
+    pub mint_config: Option<Account<'info, MintConfig>>,
+
+    /// Token account holding a token round's bets, owned by the vault PDA
+    #[account(
+        init,
+        payer = admin,
+        token::mint = stake_mint,
+        token::authority = vault,
+        seeds = [Round::TOKEN_VAULT_SEED, config.round_counter.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub vault_token: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Pyth price update account for the round's asset (if Pyth is a source)
+    /// CHECK: Owner and layout validated by oracle::pyth::read_price
+    pub price_feed: Option<UncheckedAccount<'info>>,
+
+    /// Switchboard aggregator for the round's asset (if Switchboard is a source)
+    /// CHECK: Owner and layout validated by oracle::switchboard::read_price
+    pub switchboard_feed: Option<UncheckedAccount<'info>>,
+
+    /// Instructions sysvar, used to verify price attestations
+    /// CHECK: Address checked against the sysvar id
+    #[account(address = sysvar_instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+
     #[account(mut)]
     pub admin: Signer<'info>,
 
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+
     pub system_program: Program<'info, System>,
 }
 
-/// Start a new round with admin-provided start price (fetched from CoinGecko/other API)
-/// Round cycle: 12h betting + 12h waiting = 24h total
-pub fn handler(ctx: Context<StartRound>, asset_symbol: String, start_price: i64) -> Result<()> {
+/// Start a new round priced by the median of one to three sources
+/// (Pyth feed, Switchboard aggregator, price attested by the price signers)
+/// Round cycle: betting window, then waiting period until settlement
+/// (e.g. 12h + 12h for the daily candle), each within the config bounds
+///
+/// Passing a stake mint (with its `MintConfig`, a vault token account and
+/// the token program) makes it a token round: bets, fees and payouts move in
+/// that token instead of lamports. Token rounds cannot roll a tied pool into
+/// another round, so a `Rollover` tie policy is applied as `Refund`.
+#[allow(clippy::too_many_arguments)]
+pub fn handler(
+    ctx: Context<StartRound>,
+    asset_symbol: String,
+    price_sources: Vec<PriceSource>,
+    oracle_quorum: u8,
+    attested_price: Option<Price>,
+    twap_samples: u8,
+    betting_duration_secs: i64,
+    waiting_duration_secs: i64,
+) -> Result<()> {
     // Validate asset symbol is not empty and not too long
     require!(!asset_symbol.is_empty() && asset_symbol.len() <= 16, LeftRightError::InvalidAssetSymbol);
 
+    // Validate durations against the config bounds
+    let config = &ctx.accounts.config;
+    require!(
+        betting_duration_secs >= config.min_betting_secs
+            && betting_duration_secs <= config.max_betting_secs,
+        LeftRightError::InvalidRoundDuration
+    );
+    require!(
+        waiting_duration_secs >= config.min_waiting_secs
+            && waiting_duration_secs <= config.max_waiting_secs,
+        LeftRightError::InvalidRoundDuration
+    );
+
+    // A token round needs its limits, vault and the token program, and
+    // cannot take over lamports rolled over into this round's vault
+    let stake_mint = ctx.accounts.stake_mint.as_ref().map(|mint| mint.key());
+    if stake_mint.is_some() {
+        require!(
+            ctx.accounts.mint_config.is_some()
+                && ctx.accounts.vault_token.is_some()
+                && ctx.accounts.token_program.is_some(),
+            LeftRightError::TokenAccountRequired
+        );
+        require!(config.rollover_pool == 0, LeftRightError::RolloverPending);
+    }
+
+    // Validate source set: 1-3 distinct sources, quorum within range
+    require!(
+        !price_sources.is_empty() && price_sources.len() <= MAX_PRICE_SOURCES,
+        LeftRightError::InvalidPriceSources
+    );
+    for (i, source) in price_sources.iter().enumerate() {
+        require!(!price_sources[..i].contains(source), LeftRightError::InvalidPriceSources);
+    }
+    require!(
+        oracle_quorum > 0 && (oracle_quorum as usize) <= price_sources.len(),
+        LeftRightError::InvalidPriceSources
+    );
+
+    // Feed accounts must be passed exactly for the sources in use
+    require!(
+        ctx.accounts.price_feed.is_some() == price_sources.contains(&PriceSource::Pyth),
+        LeftRightError::InvalidPriceSource
+    );
+    require!(
+        ctx.accounts.switchboard_feed.is_some() == price_sources.contains(&PriceSource::Switchboard),
+        LeftRightError::InvalidPriceSource
+    );
+    require!(
+        attested_price.is_none() || price_sources.contains(&PriceSource::Attestation),
+        LeftRightError::InvalidPriceSource
+    );
+
+    // TWAP settlement samples the Pyth feed, so it needs Pyth as a source
+    if twap_samples > 0 {
+        require!(price_sources.contains(&PriceSource::Pyth), LeftRightError::InvalidPriceSource);
+        require!(
+            twap_samples >= config.twap_min_samples
+                && (twap_samples as usize) <= PriceSamples::MAX_SAMPLES,
+            LeftRightError::InvalidTwapConfig
+        );
+    }
+
     let clock = Clock::get()?;
     let start_time = clock.unix_timestamp;
-    let betting_end_time = start_time + Round::BETTING_DURATION; // 12h betting window
-    let end_time = start_time + Round::ROUND_DURATION; // 24h total (12h betting + 12h waiting)
+
+    // Later Pyth reads must carry the same feed as the start price
+    let price_feed_id = ctx.accounts.price_feed
+        .as_ref()
+        .map(|feed| pyth::read_feed_id(feed))
+        .transpose()?
+        .unwrap_or_default();
+
+    // Collect one reading per source; stale or wide readings are dropped
+    let mut readings = PriceReadings::default();
+    for source in price_sources.iter().copied() {
+        let reading = match source {
+            PriceSource::Pyth => ctx.accounts.price_feed
+                .as_ref()
+                .map(|feed| pyth::read_price(feed, &price_feed_id))
+                .transpose()?,
+            PriceSource::Switchboard => ctx.accounts.switchboard_feed
+                .as_ref()
+                .map(|feed| switchboard::read_price(feed))
+                .transpose()?,
+            PriceSource::Attestation => match attested_price {
+                Some(price) => {
+                    attestation::verify_attestation(
+                        &ctx.accounts.instructions_sysvar,
+                        config,
+                        &PriceAttestation {
+                            round_id: config.round_counter,
+                            kind: AttestationKind::Start,
+                            asset_symbol: asset_symbol.clone(),
+                            price,
+                        },
+                    )?;
+                    Some(price)
+                }
+                None => None,
+            },
+        };
+
+        if let Some(price) = reading {
+            oracle::record_reading(&mut readings, source, price, config, start_time, i64::MIN);
+        }
+    }
+
+    let start_price = oracle::median_price(&readings, oracle_quorum)?;
+    let price_feed = ctx.accounts.price_feed
+        .as_ref()
+        .map(|feed| feed.key())
+        .unwrap_or_default();
+    let switchboard_feed = ctx.accounts.switchboard_feed
+        .as_ref()
+        .map(|feed| feed.key())
+        .unwrap_or_default();
+
+    let betting_end_time = start_time
+        .checked_add(betting_duration_secs)
+        .ok_or(LeftRightError::MathOverflow)?;
+    let end_time = betting_end_time
+        .checked_add(waiting_duration_secs)
+        .ok_or(LeftRightError::MathOverflow)?;
 
     // Initialize round
     let round = &mut ctx.accounts.round;
@@ -55,8 +237,22 @@ pub fn handler(ctx: Context<StartRound>, asset_symbol: String, start_price: i64)
 
     round.round_id = config.round_counter;
     round.asset_symbol = asset_symbol.clone();
+    round.price_sources = price_sources.clone();
+    round.oracle_quorum = oracle_quorum;
+    round.price_feed = price_feed;
+    round.price_feed_id = price_feed_id;
+    round.switchboard_feed = switchboard_feed;
+    round.twap_samples = twap_samples;
+    round.tie_policy = match (stake_mint, config.tie_policy) {
+        (Some(_), TiePolicy::Rollover) => TiePolicy::Refund,
+        (_, tie_policy) => tie_policy,
+    };
+    round.weight_curve = config.weight_curve.clone();
+    round.stake_mint = stake_mint;
     round.start_price = start_price;
-    round.end_price = 0;
+    round.end_price = Price::default();
+    round.start_readings = readings;
+    round.end_readings = PriceReadings::default();
     round.start_time = start_time;
     round.betting_end_time = betting_end_time;
     round.end_time = end_time;
@@ -65,11 +261,36 @@ pub fn handler(ctx: Context<StartRound>, asset_symbol: String, start_price: i64)
     round.right_pool = 0;
     round.left_weighted_pool = 0;
     round.right_weighted_pool = 0;
+    // Pools rolled over from tied rounds already sit in this round's vault
+    round.bonus_pool = config.rollover_pool;
     round.bet_count = 0;
     round.payouts_processed = 0;
+    round.bets_closed = 0;
     round.winning_side = None;
+    round.proposer = Pubkey::default();
+    round.challenge_end_time = 0;
+    round.disputer = None;
+    round.settlement_bond = 0;
     round.bump = ctx.bumps.round;
 
+    config.rollover_pool = 0;
+
+    // Keep the SOL vault rent-exempt however its payouts round, so the last
+    // one never leaves it with dust below the minimum; `sweep_vault` sends
+    // the reserve to the treasury with the dust
+    if stake_mint.is_none() {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.admin.to_account_info(),
+                    to: ctx.accounts.vault.to_account_info(),
+                },
+            ),
+            Rent::get()?.minimum_balance(0),
+        )?;
+    }
+
     // Increment round counter
     config.round_counter = config.round_counter.checked_add(1)
         .ok_or(LeftRightError::MathOverflow)?;
@@ -77,8 +298,20 @@ pub fn handler(ctx: Context<StartRound>, asset_symbol: String, start_price: i64)
     emit!(RoundStarted {
         round_id: round.round_id,
         asset_symbol,
+        price_sources,
+        oracle_quorum,
+        price_feed,
+        price_feed_id,
+        switchboard_feed,
+        twap_samples,
+        tie_policy: round.tie_policy,
+        weight_curve: round.weight_curve.clone(),
+        stake_mint,
+        bonus_pool: round.bonus_pool,
         start_price,
+        start_readings: readings,
         start_time,
+        betting_end_time,
         end_time,
     });
 
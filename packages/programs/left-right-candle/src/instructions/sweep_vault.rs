@@ -0,0 +1,98 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::errors::LeftRightError;
+use crate::events::VaultSwept;
+use crate::stake::Stake;
+use crate::state::{Config, Round};
+
+#[derive(Accounts)]
+pub struct SweepVault<'info> {
+    #[account(
+        seeds = [Config::SEED],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        seeds = [Round::SEED, round.round_id.to_le_bytes().as_ref()],
+        bump = round.bump,
+    )]
+    pub round: Account<'info, Round>,
+
+    /// Round vault PDA
+    /// CHECK: PDA owned by program
+    #[account(
+        mut,
+        seeds = [b"vault", round.round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    /// Treasury receiving the residual lamports
+    /// CHECK: Validated against config
+    #[account(
+        mut,
+        constraint = treasury.key() == config.treasury @ LeftRightError::Unauthorized
+    )]
+    pub treasury: UncheckedAccount<'info>,
+
+    /// Stake mint of a token round (fees withheld by the vault are
+    /// harvested to it before the vault closes)
+    #[account(
+        mut,
+        constraint = round.stake_mint == Some(stake_mint.key()) @ LeftRightError::InvalidStakeMint
+    )]
+    pub stake_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    /// Token account of a token round the vault PDA holds bets in
+    #[account(
+        mut,
+        seeds = [Round::TOKEN_VAULT_SEED, round.round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub vault_token: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Treasury's token account receiving the residual tokens (token rounds)
+    #[account(mut)]
+    pub treasury_token: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Send what is left in a finished round's vault (payout rounding dust, and
+/// the rent-exempt reserve of a SOL round) to the treasury (permissionless).
+/// A token round's vault token account is closed as well, its rent going to
+/// the treasury.
+pub fn handler(ctx: Context<SweepVault>) -> Result<()> {
+    let round = &ctx.accounts.round;
+
+    require!(round.is_finished(), LeftRightError::RoundNotFinished);
+
+    let stake = Stake::new(
+        round,
+        &ctx.accounts.vault,
+        ctx.bumps.vault,
+        &ctx.accounts.stake_mint,
+        &ctx.accounts.vault_token,
+        &ctx.accounts.token_program,
+        &ctx.accounts.system_program,
+    );
+    let treasury = ctx.accounts.treasury.to_account_info();
+
+    let amount = stake.vault_balance()?;
+    stake.pay_out(&treasury, ctx.accounts.treasury_token.as_ref(), amount)?;
+
+    if stake.is_token() {
+        stake.close_vault_token(&treasury)?;
+    }
+
+    emit!(VaultSwept {
+        round_id: round.round_id,
+        amount,
+    });
+
+    Ok(())
+}
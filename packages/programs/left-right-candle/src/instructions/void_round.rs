@@ -0,0 +1,156 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+use crate::errors::LeftRightError;
+use crate::events::RoundVoided;
+use crate::instructions::settle_round::roll_over_pool;
+use crate::state::{Config, Round, RoundStatus};
+
+#[derive(Accounts)]
+pub struct VoidRound<'info> {
+    #[account(
+        mut,
+        seeds = [Config::SEED],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [Round::SEED, round.round_id.to_le_bytes().as_ref()],
+        bump = round.bump,
+    )]
+    pub round: Account<'info, Round>,
+
+    /// Round vault PDA holding the pool
+    /// CHECK: PDA owned by program
+    #[account(
+        mut,
+        seeds = [b"vault", round.round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    /// Vault of the next round to start, receives the round's bonus pool
+    /// CHECK: PDA owned by program
+    #[account(
+        mut,
+        seeds = [b"vault", config.round_counter.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub next_vault: SystemAccount<'info>,
+
+    /// Bond vault PDA holding the settlement bonds of a disputed round
+    /// CHECK: PDA owned by program
+    #[account(
+        mut,
+        seeds = [Round::BOND_SEED, round.round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub bond_vault: SystemAccount<'info>,
+
+    /// Proposer of a disputed round, getting their bond back
+    /// CHECK: Validated against round record
+    #[account(
+        mut,
+        constraint = proposer.key() == round.proposer @ LeftRightError::Unauthorized
+    )]
+    pub proposer: Option<UncheckedAccount<'info>>,
+
+    /// Disputer of a disputed round, getting their bond back
+    /// CHECK: Validated against round record
+    #[account(
+        mut,
+        constraint = Some(disputer.key()) == round.disputer @ LeftRightError::Unauthorized
+    )]
+    pub disputer: Option<UncheckedAccount<'info>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Void a round nobody settled within the grace period (permissionless)
+///
+/// The round switches to `Refunding`, so every bet can be refunded with
+/// `process_payout`. A bonus pool rolled over from earlier ties moves on to
+/// the next round.
+///
+/// A disputed round nobody resolved can be voided once the grace period has
+/// passed since its challenge window closed. Neither side was proven right,
+/// so the proposer and disputer both get their bonds back.
+pub fn handler(ctx: Context<VoidRound>) -> Result<()> {
+    let round = &mut ctx.accounts.round;
+    let clock = Clock::get()?;
+
+    let disputed = round.status == RoundStatus::Disputed;
+    require!(
+        round.status == RoundStatus::Open || round.status == RoundStatus::Locked || disputed,
+        LeftRightError::RoundAlreadySettled
+    );
+
+    let grace_start = if disputed { round.challenge_end_time } else { round.end_time };
+    let void_time = grace_start
+        .checked_add(ctx.accounts.config.void_grace_secs)
+        .ok_or(LeftRightError::MathOverflow)?;
+    require!(
+        clock.unix_timestamp >= void_time,
+        LeftRightError::VoidGracePeriodActive
+    );
+
+    if disputed {
+        let proposer = ctx.accounts.proposer
+            .as_ref()
+            .ok_or(LeftRightError::Unauthorized)?;
+        let disputer = ctx.accounts.disputer
+            .as_ref()
+            .ok_or(LeftRightError::Unauthorized)?;
+
+        let round_id_bytes = round.round_id.to_le_bytes();
+        let bond_seeds = &[
+            Round::BOND_SEED,
+            round_id_bytes.as_ref(),
+            &[ctx.bumps.bond_vault],
+        ];
+
+        for party in [proposer.to_account_info(), disputer.to_account_info()] {
+            system_program::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.bond_vault.to_account_info(),
+                        to: party,
+                    },
+                    &[bond_seeds],
+                ),
+                round.settlement_bond,
+            )?;
+        }
+    }
+
+    let bonus_pool = round.bonus_pool;
+    roll_over_pool(
+        round,
+        &mut ctx.accounts.config,
+        bonus_pool,
+        ctx.accounts.vault.to_account_info(),
+        ctx.bumps.vault,
+        ctx.accounts.next_vault.to_account_info(),
+        ctx.accounts.system_program.to_account_info(),
+    )?;
+
+    round.winning_side = None;
+    round.status = if round.bet_count == 0 {
+        RoundStatus::Settled
+    } else {
+        RoundStatus::Refunding
+    };
+
+    emit!(RoundVoided {
+        round_id: round.round_id,
+        end_time: round.end_time,
+        voided_at: clock.unix_timestamp,
+        total_pool: round.total_pool(),
+        bet_count: round.bet_count,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,172 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::errors::LeftRightError;
+use crate::events::BetWithdrawn;
+use crate::stake::Stake;
+use crate::state::{Bet, Config, Round, Side};
+
+#[derive(Accounts)]
+pub struct WithdrawBet<'info> {
+    #[account(
+        seeds = [Config::SEED],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [Round::SEED, round.round_id.to_le_bytes().as_ref()],
+        bump = round.bump,
+    )]
+    pub round: Account<'info, Round>,
+
+    #[account(
+        mut,
+        seeds = [
+            Bet::SEED,
+            round.round_id.to_le_bytes().as_ref(),
+            bettor.key().as_ref()
+        ],
+        bump = bet.bump,
+        constraint = bet.round_id == round.round_id,
+    )]
+    pub bet: Account<'info, Bet>,
+
+    /// Round vault PDA holding pool funds
+    /// CHECK: PDA owned by program
+    #[account(
+        mut,
+        seeds = [b"vault", round.round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    /// Treasury receiving the exit penalty (if configured)
+    /// CHECK: Validated against config
+    #[account(
+        mut,
+        constraint = treasury.key() == config.treasury @ LeftRightError::Unauthorized
+    )]
+    pub treasury: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub bettor: Signer<'info>,
+
+    /// Stake mint of a token round
+    #[account(
+        constraint = round.stake_mint == Some(stake_mint.key()) @ LeftRightError::InvalidStakeMint
+    )]
+    pub stake_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    /// Token account of a token round the vault PDA holds bets in
+    #[account(
+        mut,
+        seeds = [Round::TOKEN_VAULT_SEED, round.round_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub vault_token: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Bettor's token account receiving the refund (token rounds)
+    #[account(mut)]
+    pub bettor_token: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Treasury's token account receiving the penalty (token rounds)
+    #[account(mut)]
+    pub treasury_token: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Cash out a position while betting is still open
+///
+/// The bettor gets their pool contribution back minus the exit penalty,
+/// which either stays in the round as bonus for the winners or goes to the
+/// treasury. Fees paid at bet time are not returned. The position is marked
+/// withdrawn and skipped by payouts and refunds.
+pub fn handler(ctx: Context<WithdrawBet>) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let round = &mut ctx.accounts.round;
+    let bet = &mut ctx.accounts.bet;
+    let clock = Clock::get()?;
+
+    require!(
+        round.is_betting_open(clock.unix_timestamp),
+        LeftRightError::BettingPeriodEnded
+    );
+    require!(!bet.withdrawn, LeftRightError::BetWithdrawn);
+
+    let penalty = (bet.amount as u128)
+        .checked_mul(config.exit_penalty_bps as u128)
+        .and_then(|v| v.checked_div(10000))
+        .ok_or(LeftRightError::MathOverflow)? as u64;
+    let refund = bet.amount
+        .checked_sub(penalty)
+        .ok_or(LeftRightError::MathOverflow)?;
+
+    // Take the position out of the pools, exactly as it was added
+    match bet.side_enum().ok_or(LeftRightError::InvalidSide)? {
+        Side::Left => {
+            round.left_pool = round.left_pool
+                .checked_sub(bet.amount)
+                .ok_or(LeftRightError::MathOverflow)?;
+            round.left_weighted_pool = round.left_weighted_pool
+                .checked_sub(bet.weighted_amount)
+                .ok_or(LeftRightError::MathOverflow)?;
+        }
+        Side::Right => {
+            round.right_pool = round.right_pool
+                .checked_sub(bet.amount)
+                .ok_or(LeftRightError::MathOverflow)?;
+            round.right_weighted_pool = round.right_weighted_pool
+                .checked_sub(bet.weighted_amount)
+                .ok_or(LeftRightError::MathOverflow)?;
+        }
+    }
+
+    let stake = Stake::new(
+        round,
+        &ctx.accounts.vault,
+        ctx.bumps.vault,
+        &ctx.accounts.stake_mint,
+        &ctx.accounts.vault_token,
+        &ctx.accounts.token_program,
+        &ctx.accounts.system_program,
+    );
+
+    stake.pay_out(
+        &ctx.accounts.bettor.to_account_info(),
+        ctx.accounts.bettor_token.as_ref(),
+        refund,
+    )?;
+
+    // Penalty goes to the treasury, or stays in the vault for the winners
+    if penalty > 0 {
+        if config.exit_penalty_to_treasury {
+            stake.pay_out(
+                &ctx.accounts.treasury.to_account_info(),
+                ctx.accounts.treasury_token.as_ref(),
+                penalty,
+            )?;
+        } else {
+            round.bonus_pool = round.bonus_pool
+                .checked_add(penalty)
+                .ok_or(LeftRightError::MathOverflow)?;
+        }
+    }
+
+    bet.withdrawn = true;
+
+    emit!(BetWithdrawn {
+        round_id: round.round_id,
+        bettor: bet.bettor,
+        side: bet.side,
+        amount: refund,
+        penalty,
+        penalty_to_treasury: config.exit_penalty_to_treasury,
+    });
+
+    Ok(())
+}
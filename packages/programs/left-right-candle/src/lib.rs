@@ -3,9 +3,13 @@ use anchor_lang::prelude::*;
 pub mod errors;
 pub mod events;
 pub mod instructions;
+pub mod merkle;
+pub mod oracle;
+pub mod stake;
 pub mod state;
 
 use instructions::*;
+use state::{Price, PriceSource, TiePolicy, WeightCurve};
 
 declare_id!("81K7nKnv7JiRhBCRNmagKot27Yu82eRWeeNA7dtGGaX6");
 
@@ -24,31 +28,294 @@ pub mod left_right_candle {
         instructions::initialize::handler(ctx, fee_bps, referrer_fee_bps, min_bet_lamports, max_bet_lamports)
     }
 
-    /// Start a new betting round with admin-provided start price
+    /// Update oracle staleness and confidence limits
+    pub fn set_oracle_config(
+        ctx: Context<SetOracleConfig>,
+        max_price_age_secs: i64,
+        max_conf_bps: u16,
+    ) -> Result<()> {
+        instructions::set_oracle_config::handler(ctx, max_price_age_secs, max_conf_bps)
+    }
+
+    /// Replace the set of keys allowed to attest prices
+    pub fn set_price_signers(
+        ctx: Context<SetPriceSigners>,
+        price_signers: Vec<Pubkey>,
+        signer_threshold: u8,
+    ) -> Result<()> {
+        instructions::set_price_signers::handler(ctx, price_signers, signer_threshold)
+    }
+
+    /// Configure the optimistic settlement challenge window and bond
+    pub fn set_dispute_config(
+        ctx: Context<SetDisputeConfig>,
+        dispute_window_secs: i64,
+        settlement_bond_lamports: u64,
+    ) -> Result<()> {
+        instructions::set_dispute_config::handler(ctx, dispute_window_secs, settlement_bond_lamports)
+    }
+
+    /// Configure TWAP sample count and spacing limits
+    pub fn set_twap_config(
+        ctx: Context<SetTwapConfig>,
+        twap_min_samples: u8,
+        twap_min_spacing_secs: i64,
+    ) -> Result<()> {
+        instructions::set_twap_config::handler(ctx, twap_min_samples, twap_min_spacing_secs)
+    }
+
+    /// Set what happens when a round ends exactly at its start price
+    pub fn set_tie_policy(ctx: Context<SetTiePolicy>, tie_policy: TiePolicy) -> Result<()> {
+        instructions::set_tie_policy::handler(ctx, tie_policy)
+    }
+
+    /// Choose whether refunds also return the treasury fee
+    pub fn set_refund_fees(ctx: Context<SetRefundFees>, refund_fees: bool) -> Result<()> {
+        instructions::set_refund_fees::handler(ctx, refund_fees)
+    }
+
+    /// Set the grace period after which an unsettled round can be voided
+    pub fn set_void_grace_period(ctx: Context<SetVoidGracePeriod>, void_grace_secs: i64) -> Result<()> {
+        instructions::set_void_grace_period::handler(ctx, void_grace_secs)
+    }
+
+    /// Set the range of betting and waiting durations rounds can use
+    pub fn set_duration_bounds(
+        ctx: Context<SetDurationBounds>,
+        min_betting_secs: i64,
+        max_betting_secs: i64,
+        min_waiting_secs: i64,
+        max_waiting_secs: i64,
+    ) -> Result<()> {
+        instructions::set_duration_bounds::handler(
+            ctx,
+            min_betting_secs,
+            max_betting_secs,
+            min_waiting_secs,
+            max_waiting_secs,
+        )
+    }
+
+    /// Set the early-bird weight curve used by rounds started from now on
+    pub fn set_weight_curve(ctx: Context<SetWeightCurve>, weight_curve: WeightCurve) -> Result<()> {
+        instructions::set_weight_curve::handler(ctx, weight_curve)
+    }
+
+    /// Set the per-wallet caps per round and per rolling 24 hours (0 = no cap)
+    pub fn set_exposure_limits(
+        ctx: Context<SetExposureLimits>,
+        max_round_exposure_lamports: u64,
+        max_daily_exposure_lamports: u64,
+    ) -> Result<()> {
+        instructions::set_exposure_limits::handler(
+            ctx,
+            max_round_exposure_lamports,
+            max_daily_exposure_lamports,
+        )
+    }
+
+    /// Limit bets to max(floor, a share of the current pool); 0 bps disables
+    pub fn set_dynamic_max_bet(
+        ctx: Context<SetDynamicMaxBet>,
+        dynamic_max_bet_bps: u16,
+        dynamic_max_bet_floor_lamports: u64,
+    ) -> Result<()> {
+        instructions::set_dynamic_max_bet::handler(
+            ctx,
+            dynamic_max_bet_bps,
+            dynamic_max_bet_floor_lamports,
+        )
+    }
+
+    /// Enable a stake mint for token rounds, or update its bet limits
+    pub fn set_mint_config(
+        ctx: Context<SetMintConfig>,
+        min_bet: u64,
+        max_bet: u64,
+        max_round_exposure: u64,
+        max_daily_exposure: u64,
+        dynamic_max_bet_floor: u64,
+    ) -> Result<()> {
+        instructions::set_mint_config::handler(
+            ctx,
+            min_bet,
+            max_bet,
+            max_round_exposure,
+            max_daily_exposure,
+            dynamic_max_bet_floor,
+        )
+    }
+
+    /// Set the early withdrawal penalty and where it goes
+    pub fn set_exit_penalty(
+        ctx: Context<SetExitPenalty>,
+        exit_penalty_bps: u16,
+        exit_penalty_to_treasury: bool,
+    ) -> Result<()> {
+        instructions::set_exit_penalty::handler(ctx, exit_penalty_bps, exit_penalty_to_treasury)
+    }
+
+    /// Start a new betting round, priced by the median of up to three
+    /// sources (Pyth, Switchboard, signed price attestation), staked in SOL
+    /// or in an enabled SPL token
+    #[allow(clippy::too_many_arguments)]
     pub fn start_round(
         ctx: Context<StartRound>,
         asset_symbol: String,
-        start_price: i64,
+        price_sources: Vec<PriceSource>,
+        oracle_quorum: u8,
+        attested_price: Option<Price>,
+        twap_samples: u8,
+        betting_duration_secs: i64,
+        waiting_duration_secs: i64,
     ) -> Result<()> {
-        instructions::start_round::handler(ctx, asset_symbol, start_price)
+        instructions::start_round::handler(
+            ctx,
+            asset_symbol,
+            price_sources,
+            oracle_quorum,
+            attested_price,
+            twap_samples,
+            betting_duration_secs,
+            waiting_duration_secs,
+        )
     }
 
-    /// Place a bet on a round
+    /// Place a bet on a round, or top up an existing position on the same
+    /// side, optionally bounding the implied payout multiplier (slippage)
     pub fn place_bet(
         ctx: Context<PlaceBet>,
         side: u8,
         amount_lamports: u64,
+        min_multiplier_bps: Option<u64>,
     ) -> Result<()> {
-        instructions::place_bet::handler(ctx, side, amount_lamports)
+        instructions::place_bet::handler(ctx, side, amount_lamports, min_multiplier_bps)
+    }
+
+    /// Cash out a position before betting closes, minus the exit penalty
+    pub fn withdraw_bet(ctx: Context<WithdrawBet>) -> Result<()> {
+        instructions::withdraw_bet::handler(ctx)
+    }
+
+    /// Close betting after the betting window (permissionless)
+    pub fn lock_round(ctx: Context<LockRound>) -> Result<()> {
+        instructions::lock_round::handler(ctx)
+    }
+
+    /// Record a TWAP price sample during the waiting period (permissionless)
+    pub fn record_price_sample(ctx: Context<RecordPriceSample>) -> Result<()> {
+        instructions::record_price_sample::handler(ctx)
+    }
+
+    /// Settle a round (permissionless) with the median end price of the
+    /// round's price sources
+    pub fn settle_round(ctx: Context<SettleRound>, attested_price: Option<Price>) -> Result<()> {
+        instructions::settle_round::handler(ctx, attested_price)
     }
 
-    /// Settle a round with admin-provided end price
-    pub fn settle_round(ctx: Context<SettleRound>, end_price: i64) -> Result<()> {
-        instructions::settle_round::handler(ctx, end_price)
+    /// Dispute a proposed end price by posting a matching bond
+    pub fn dispute_settlement(ctx: Context<DisputeSettlement>) -> Result<()> {
+        instructions::dispute_settlement::handler(ctx)
+    }
+
+    /// Finalize an undisputed proposal after the challenge window (permissionless)
+    pub fn finalize_settlement(ctx: Context<FinalizeSettlement>) -> Result<()> {
+        instructions::finalize_settlement::handler(ctx)
+    }
+
+    /// Resolve a disputed settlement (admin or price signer quorum)
+    pub fn resolve_dispute(ctx: Context<ResolveDispute>, end_price: Price) -> Result<()> {
+        instructions::resolve_dispute::handler(ctx, end_price)
     }
 
     /// Process a single payout (called in a loop by the settler)
     pub fn process_payout(ctx: Context<ProcessPayout>) -> Result<()> {
         instructions::process_payout::handler(ctx)
     }
+
+    /// Process payouts for the (bet, bettor) groups in remaining accounts,
+    /// as many as fit in compute
+    pub fn process_payouts_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ProcessPayoutsBatch<'info>>,
+    ) -> Result<()> {
+        instructions::process_payouts_batch::handler(ctx)
+    }
+
+    /// Claim a bet's own payout or refund, signed by the bettor
+    pub fn claim(ctx: Context<Claim>) -> Result<()> {
+        instructions::claim::handler(ctx)
+    }
+
+    /// Post a large round's payouts as a merkle root, for winners to claim
+    pub fn post_payout_root(
+        ctx: Context<PostPayoutRoot>,
+        merkle_root: [u8; 32],
+        total_payout: u64,
+        leaf_count: u32,
+        claim_window_secs: i64,
+    ) -> Result<()> {
+        instructions::post_payout_root::handler(
+            ctx,
+            merkle_root,
+            total_payout,
+            leaf_count,
+            claim_window_secs,
+        )
+    }
+
+    /// Claim a payout from a round settled by merkle root
+    pub fn claim_with_proof(
+        ctx: Context<ClaimWithProof>,
+        index: u32,
+        amount: u64,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        instructions::claim_with_proof::handler(ctx, index, amount, proof)
+    }
+
+    /// Finalize a merkle-settled round after its claim deadline (or once
+    /// fully claimed), closing its payout root (permissionless)
+    pub fn close_payout_root(ctx: Context<ClosePayoutRoot>) -> Result<()> {
+        instructions::close_payout_root::handler(ctx)
+    }
+
+    /// Cancel a round before settlement so every bet can be refunded
+    pub fn cancel_round(ctx: Context<CancelRound>) -> Result<()> {
+        instructions::cancel_round::handler(ctx)
+    }
+
+    /// Refund a bet of a cancelled round and close it (permissionless)
+    pub fn refund_bet(ctx: Context<RefundBet>) -> Result<()> {
+        instructions::refund_bet::handler(ctx)
+    }
+
+    /// Pay back a treasury fee a refund left owed, signed by the treasury
+    pub fn settle_fee_refund(ctx: Context<SettleFeeRefund>) -> Result<()> {
+        instructions::settle_fee_refund::handler(ctx)
+    }
+
+    /// Void a round left unsettled past the grace period (permissionless)
+    pub fn void_round(ctx: Context<VoidRound>) -> Result<()> {
+        instructions::void_round::handler(ctx)
+    }
+
+    /// Close a paid-out bet, returning its rent to the bettor (permissionless)
+    pub fn close_bet(ctx: Context<CloseBet>) -> Result<()> {
+        instructions::close_bet::handler(ctx)
+    }
+
+    /// Send a finished round's residual vault balance to the treasury (permissionless)
+    pub fn sweep_vault(ctx: Context<SweepVault>) -> Result<()> {
+        instructions::sweep_vault::handler(ctx)
+    }
+
+    /// Close a round's TWAP sample buffer after settlement (permissionless)
+    pub fn close_price_samples(ctx: Context<ClosePriceSamples>) -> Result<()> {
+        instructions::close_price_samples::handler(ctx)
+    }
+
+    /// Close a finished round once its bets are closed and vault swept
+    pub fn close_round(ctx: Context<CloseRound>) -> Result<()> {
+        instructions::close_round::handler(ctx)
+    }
 }
@@ -0,0 +1,35 @@
+//! Merkle proofs for payouts posted as a root (see `state::PayoutRoot`)
+//!
+//! Leaves and inner nodes are sha256 hashes with distinct prefixes, so a
+//! node can never pass as a leaf. Pairs are hashed in sorted order, so a
+//! proof is just the list of sibling hashes from leaf to root.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+
+const LEAF_PREFIX: &[u8] = &[0];
+const NODE_PREFIX: &[u8] = &[1];
+
+/// Leaf for `bettor` being owed `amount`, at position `index` in the tree
+pub fn payout_leaf(index: u32, bettor: &Pubkey, amount: u64) -> [u8; 32] {
+    hashv(&[
+        LEAF_PREFIX,
+        &index.to_le_bytes(),
+        bettor.as_ref(),
+        &amount.to_le_bytes(),
+    ])
+    .to_bytes()
+}
+
+/// Whether `proof` leads from `leaf` to `root`
+pub fn verify(proof: &[[u8; 32]], root: &[u8; 32], leaf: [u8; 32]) -> bool {
+    let computed = proof.iter().fold(leaf, |node, sibling| {
+        let (first, second) = if node <= *sibling {
+            (&node, sibling)
+        } else {
+            (sibling, &node)
+        };
+        hashv(&[NODE_PREFIX, first, second]).to_bytes()
+    });
+    computed == *root
+}
@@ -0,0 +1,130 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::ed25519_program;
+use anchor_lang::solana_program::sysvar::instructions::{
+    load_current_index_checked, load_instruction_at_checked,
+};
+
+use crate::errors::LeftRightError;
+use crate::state::{Config, Price};
+
+/// Domain separator prepended to every attestation message
+pub const ATTESTATION_DOMAIN: &[u8] = b"left-right-candle:price:v1";
+
+/// Size of the Ed25519 instruction header and of each signature offsets entry
+const ED25519_HEADER_LEN: usize = 2;
+const ED25519_OFFSETS_LEN: usize = 14;
+
+/// Which end of the round a price attestation is for
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum AttestationKind {
+    Start,
+    End,
+}
+
+/// Price statement signed off-chain by the configured price signers
+///
+/// Binds the round, asset and price so a signature can't be replayed on
+/// another round, another asset or the other end of the same round.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct PriceAttestation {
+    pub round_id: u64,
+    pub kind: AttestationKind,
+    pub asset_symbol: String,
+    pub price: Price,
+}
+
+impl PriceAttestation {
+    /// Bytes the price signers sign:
+    /// `ATTESTATION_DOMAIN || program_id || borsh(PriceAttestation)`
+    pub fn message(&self) -> Result<Vec<u8>> {
+        let mut message = Vec::with_capacity(ATTESTATION_DOMAIN.len() + 32 + 64);
+        message.extend_from_slice(ATTESTATION_DOMAIN);
+        message.extend_from_slice(crate::ID.as_ref());
+        self.serialize(&mut message)?;
+        Ok(message)
+    }
+}
+
+/// Check that at least `config.signer_threshold` distinct price signers signed
+/// `attestation`, using the Ed25519 program instructions that precede the
+/// current instruction in the transaction.
+pub fn verify_attestation(
+    instructions_sysvar: &AccountInfo,
+    config: &Config,
+    attestation: &PriceAttestation,
+) -> Result<()> {
+    require!(config.signer_threshold > 0, LeftRightError::InsufficientAttestations);
+
+    let message = attestation.message()?;
+    let current_index = load_current_index_checked(instructions_sysvar)?;
+    let mut signers: Vec<Pubkey> = Vec::with_capacity(config.price_signers.len());
+
+    for index in 0..current_index {
+        let ix = load_instruction_at_checked(index as usize, instructions_sysvar)?;
+        if ix.program_id != ed25519_program::ID {
+            continue;
+        }
+
+        for (signer, signed_message) in parse_ed25519_instruction(&ix.data)? {
+            if signed_message == message.as_slice()
+                && config.price_signers.contains(&signer)
+                && !signers.contains(&signer)
+            {
+                signers.push(signer);
+            }
+        }
+    }
+
+    require!(
+        signers.len() >= config.signer_threshold as usize,
+        LeftRightError::InsufficientAttestations
+    );
+
+    Ok(())
+}
+
+/// Extract (public key, message) pairs from an Ed25519 program instruction
+///
+/// Only signatures whose data lives in the instruction itself are accepted;
+/// the Ed25519 program has already verified them by the time we run.
+fn parse_ed25519_instruction(data: &[u8]) -> Result<Vec<(Pubkey, &[u8])>> {
+    require!(data.len() >= ED25519_HEADER_LEN, LeftRightError::InvalidAttestation);
+
+    let num_signatures = data[0] as usize;
+    let mut entries = Vec::with_capacity(num_signatures);
+
+    for i in 0..num_signatures {
+        let start = ED25519_HEADER_LEN + i * ED25519_OFFSETS_LEN;
+        let offsets = data
+            .get(start..start + ED25519_OFFSETS_LEN)
+            .ok_or(LeftRightError::InvalidAttestation)?;
+        let read_u16 = |at: usize| u16::from_le_bytes([offsets[at], offsets[at + 1]]);
+
+        let signature_ix_index = read_u16(2);
+        let public_key_offset = read_u16(4) as usize;
+        let public_key_ix_index = read_u16(6);
+        let message_offset = read_u16(8) as usize;
+        let message_size = read_u16(10) as usize;
+        let message_ix_index = read_u16(12);
+
+        require!(
+            signature_ix_index == u16::MAX
+                && public_key_ix_index == u16::MAX
+                && message_ix_index == u16::MAX,
+            LeftRightError::InvalidAttestation
+        );
+
+        let public_key = data
+            .get(public_key_offset..public_key_offset + 32)
+            .ok_or(LeftRightError::InvalidAttestation)?;
+        let message = data
+            .get(message_offset..message_offset + message_size)
+            .ok_or(LeftRightError::InvalidAttestation)?;
+
+        let signer = Pubkey::try_from(public_key)
+            .map_err(|_| LeftRightError::InvalidAttestation)?;
+        entries.push((signer, message));
+    }
+
+    Ok(entries)
+}
@@ -0,0 +1,96 @@
+pub mod attestation;
+pub mod pyth;
+pub mod switchboard;
+
+use anchor_lang::prelude::*;
+
+use crate::errors::LeftRightError;
+use crate::state::{Config, Price, PriceSource};
+
+/// Maximum number of price sources a round can combine
+pub const MAX_PRICE_SOURCES: usize = 3;
+
+/// One reading per price source, indexed by `PriceSource as usize`
+/// (`None` when the source is unused or its reading was rejected)
+pub type PriceReadings = [Option<Price>; MAX_PRICE_SOURCES];
+
+/// Enforce the staleness and confidence-interval limits from config
+pub fn validate_price(price: &Price, config: &Config, current_time: i64) -> Result<()> {
+    require!(price.price > 0, LeftRightError::InvalidPriceFeed);
+
+    // A publish time ahead of the clock would pass any age limit
+    require!(price.publish_time <= current_time, LeftRightError::PriceFromFuture);
+    let age = current_time.saturating_sub(price.publish_time);
+    require!(age <= config.max_price_age_secs, LeftRightError::StalePrice);
+
+    // conf / price <= max_conf_bps / 10000
+    let conf_scaled = (price.conf as u128)
+        .checked_mul(10000)
+        .ok_or(LeftRightError::MathOverflow)?;
+    let max_conf_scaled = (price.price as u128)
+        .checked_mul(config.max_conf_bps as u128)
+        .ok_or(LeftRightError::MathOverflow)?;
+    require!(
+        conf_scaled <= max_conf_scaled,
+        LeftRightError::PriceConfidenceTooWide
+    );
+
+    Ok(())
+}
+
+/// Keep a reading only if it is recent enough and passes the config guards.
+/// Rejected readings are dropped rather than failing the instruction, so a
+/// single stale source can't block a round that still has a quorum.
+pub fn record_reading(
+    readings: &mut PriceReadings,
+    source: PriceSource,
+    price: Price,
+    config: &Config,
+    current_time: i64,
+    not_before: i64,
+) {
+    if price.publish_time >= not_before
+        && validate_price(&price, config, current_time).is_ok()
+    {
+        readings[source as usize] = Some(price);
+    }
+}
+
+/// Median of the accepted readings, requiring at least `quorum` of them
+///
+/// Readings are rescaled to the smallest exponent among them. With an even
+/// count the two middle values are averaged. The result carries the widest
+/// confidence and the oldest publish time of the readings used.
+pub fn median_price(readings: &PriceReadings, quorum: u8) -> Result<Price> {
+    let valid: Vec<&Price> = readings.iter().flatten().collect();
+    require!(
+        !valid.is_empty() && valid.len() >= quorum as usize,
+        LeftRightError::OracleQuorumNotMet
+    );
+
+    let expo = valid.iter().map(|p| p.expo).min().unwrap();
+    let mut values = valid
+        .iter()
+        .map(|p| p.price_at(expo))
+        .collect::<Result<Vec<i128>>>()?;
+    values.sort_unstable();
+
+    let mid = values.len() / 2;
+    let median = if values.len() % 2 == 1 {
+        values[mid]
+    } else {
+        (values[mid - 1] + values[mid]) / 2
+    };
+
+    let mut conf: i128 = 0;
+    for p in valid.iter() {
+        conf = conf.max(p.conf_at(expo)?);
+    }
+
+    Ok(Price {
+        price: i64::try_from(median).map_err(|_| LeftRightError::MathOverflow)?,
+        conf: u64::try_from(conf).map_err(|_| LeftRightError::MathOverflow)?,
+        expo,
+        publish_time: valid.iter().map(|p| p.publish_time).min().unwrap(),
+    })
+}
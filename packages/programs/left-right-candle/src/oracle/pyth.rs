@@ -0,0 +1,89 @@
+use anchor_lang::prelude::*;
+use anchor_lang::pubkey;
+
+use crate::errors::LeftRightError;
+use crate::state::Price;
+
+/// Pyth Solana receiver program, owner of all `PriceUpdateV2` accounts
+/// (same address on mainnet and devnet)
+pub const PYTH_RECEIVER_PROGRAM_ID: Pubkey = pubkey!("rec5EKMGg6MxZYaMdyBfgwp4d5rB9T1VQH5pJv5LtFJ");
+
+/// Anchor discriminator of `PriceUpdateV2` (sha256("account:PriceUpdateV2")[0:8])
+pub const PRICE_UPDATE_V2_DISCRIMINATOR: [u8; 8] = [34, 241, 35, 99, 157, 126, 244, 205];
+
+/// Mirrors `pyth_solana_receiver_sdk::price_update::VerificationLevel`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum VerificationLevel {
+    Partial { num_signatures: u8 },
+    Full,
+}
+
+/// Mirrors `pythnet_sdk::messages::PriceFeedMessage`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct PriceFeedMessage {
+    pub feed_id: [u8; 32],
+    pub price: i64,
+    pub conf: u64,
+    pub exponent: i32,
+    pub publish_time: i64,
+    pub prev_publish_time: i64,
+    pub ema_price: i64,
+    pub ema_conf: u64,
+}
+
+/// Mirrors `pyth_solana_receiver_sdk::price_update::PriceUpdateV2`
+/// (without the discriminator)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct PriceUpdateV2 {
+    pub write_authority: Pubkey,
+    pub verification_level: VerificationLevel,
+    pub price_message: PriceFeedMessage,
+    pub posted_slot: u64,
+}
+
+/// Read the latest price from a Pyth `PriceUpdateV2` account, which must
+/// carry the feed the round was started with
+///
+/// Only fully verified updates are accepted. Staleness and confidence are
+/// checked by the caller via `oracle::validate_price`.
+pub fn read_price(price_feed: &AccountInfo, feed_id: &[u8; 32]) -> Result<Price> {
+    let message = read_message(price_feed)?;
+    require!(message.feed_id == *feed_id, LeftRightError::PriceFeedMismatch);
+
+    Ok(Price {
+        price: message.price,
+        conf: message.conf,
+        expo: message.exponent,
+        publish_time: message.publish_time,
+    })
+}
+
+/// Feed id of the update held by a Pyth `PriceUpdateV2` account. The account
+/// can be rewritten by its write authority, so rounds pin the feed id as
+/// well as the account.
+pub fn read_feed_id(price_feed: &AccountInfo) -> Result<[u8; 32]> {
+    Ok(read_message(price_feed)?.feed_id)
+}
+
+fn read_message(price_feed: &AccountInfo) -> Result<PriceFeedMessage> {
+    require!(
+        price_feed.owner == &PYTH_RECEIVER_PROGRAM_ID,
+        LeftRightError::InvalidPriceFeed
+    );
+
+    let data = price_feed.try_borrow_data()?;
+    require!(
+        data.len() > 8 && data[..8] == PRICE_UPDATE_V2_DISCRIMINATOR,
+        LeftRightError::InvalidPriceFeed
+    );
+
+    let mut payload: &[u8] = &data[8..];
+    let update = PriceUpdateV2::deserialize(&mut payload)
+        .map_err(|_| LeftRightError::InvalidPriceFeed)?;
+    require!(
+        update.verification_level == VerificationLevel::Full,
+        LeftRightError::InvalidPriceFeed
+    );
+
+    Ok(update.price_message)
+}
@@ -0,0 +1,86 @@
+use anchor_lang::prelude::*;
+use anchor_lang::pubkey;
+
+use crate::errors::LeftRightError;
+use crate::state::Price;
+
+/// Switchboard v2 program, owner of all `AggregatorAccountData` accounts
+pub const SWITCHBOARD_PROGRAM_ID: Pubkey = pubkey!("SW1TCH7qEPTdLsDHRgPuMQjbQxKdH2aBStViMFnt64f");
+
+/// Anchor discriminator of `AggregatorAccountData`
+/// (sha256("account:AggregatorAccountData")[0:8])
+pub const AGGREGATOR_DISCRIMINATOR: [u8; 8] = [217, 230, 65, 101, 201, 162, 27, 125];
+
+// Byte offsets into the (packed, zero-copy) `AggregatorAccountData` layout,
+// discriminator included. Only the fields we read are listed.
+const MIN_ORACLE_RESULTS_OFFSET: usize = 236;
+const LATEST_ROUND_OFFSET: usize = 341;
+const NUM_SUCCESS_OFFSET: usize = LATEST_ROUND_OFFSET;
+const ROUND_OPEN_TIMESTAMP_OFFSET: usize = LATEST_ROUND_OFFSET + 17;
+const RESULT_OFFSET: usize = LATEST_ROUND_OFFSET + 25;
+const STD_DEVIATION_OFFSET: usize = LATEST_ROUND_OFFSET + 45;
+const MIN_ACCOUNT_LEN: usize = STD_DEVIATION_OFFSET + 20;
+
+/// Read the latest confirmed result from a Switchboard aggregator account
+///
+/// The result's standard deviation is used as the confidence interval.
+/// Staleness and confidence are checked by the caller.
+pub fn read_price(aggregator: &AccountInfo) -> Result<Price> {
+    require!(
+        aggregator.owner == &SWITCHBOARD_PROGRAM_ID,
+        LeftRightError::InvalidPriceFeed
+    );
+
+    let data = aggregator.try_borrow_data()?;
+    require!(
+        data.len() >= MIN_ACCOUNT_LEN && data[..8] == AGGREGATOR_DISCRIMINATOR,
+        LeftRightError::InvalidPriceFeed
+    );
+
+    // The latest round must have reached the aggregator's oracle quorum
+    let min_oracle_results = read_u32(&data, MIN_ORACLE_RESULTS_OFFSET);
+    let num_success = read_u32(&data, NUM_SUCCESS_OFFSET);
+    require!(
+        num_success > 0 && num_success >= min_oracle_results,
+        LeftRightError::InvalidPriceFeed
+    );
+
+    let (mantissa, scale) = read_decimal(&data, RESULT_OFFSET);
+    let (std_mantissa, std_scale) = read_decimal(&data, STD_DEVIATION_OFFSET);
+    let conf = rescale_decimal(std_mantissa.unsigned_abs(), std_scale, scale)?;
+
+    Ok(Price {
+        price: i64::try_from(mantissa).map_err(|_| LeftRightError::InvalidPriceFeed)?,
+        conf: u64::try_from(conf).map_err(|_| LeftRightError::InvalidPriceFeed)?,
+        expo: -(i32::try_from(scale).map_err(|_| LeftRightError::InvalidPriceFeed)?),
+        publish_time: i64::from_le_bytes(
+            data[ROUND_OPEN_TIMESTAMP_OFFSET..ROUND_OPEN_TIMESTAMP_OFFSET + 8]
+                .try_into()
+                .unwrap(),
+        ),
+    })
+}
+
+fn read_u32(data: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap())
+}
+
+/// `SwitchboardDecimal { mantissa: i128, scale: u32 }`, value = mantissa / 10^scale
+fn read_decimal(data: &[u8], offset: usize) -> (i128, u32) {
+    let mantissa = i128::from_le_bytes(data[offset..offset + 16].try_into().unwrap());
+    (mantissa, read_u32(data, offset + 16))
+}
+
+/// Re-express `mantissa / 10^from_scale` with `to_scale` decimals (truncating)
+fn rescale_decimal(mantissa: u128, from_scale: u32, to_scale: u32) -> Result<u128> {
+    let value = if to_scale >= from_scale {
+        10u128
+            .checked_pow(to_scale - from_scale)
+            .and_then(|f| mantissa.checked_mul(f))
+    } else {
+        10u128
+            .checked_pow(from_scale - to_scale)
+            .map(|f| mantissa / f)
+    };
+    Ok(value.ok_or(LeftRightError::MathOverflow)?)
+}
@@ -0,0 +1,297 @@
+//! Moving a round's stake
+//!
+//! SOL rounds keep bets as lamports in the vault PDA. Token rounds keep them
+//! in a token account owned by that same PDA, so the vault seeds sign for
+//! either kind of round and instructions only differ in the accounts passed.
+//! Token rounds go through the token interface, so the stake mint can belong
+//! to either the Token or the Token-2022 program.
+
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::token_2022::spl_token_2022::extension::transfer_fee::TransferFeeAmount;
+use anchor_spl::token_2022::spl_token_2022::extension::{
+    BaseStateWithExtensions, ExtensionType, StateWithExtensions,
+};
+use anchor_spl::token_2022::spl_token_2022::state::{Account as SplAccount, Mint as SplMint};
+use anchor_spl::token_interface::{
+    self, HarvestWithheldTokensToMint, Mint, TokenAccount, TokenInterface,
+};
+
+use crate::errors::LeftRightError;
+use crate::state::Round;
+
+/// Token-2022 mint extensions token rounds refuse: a permanent delegate can
+/// move tokens out of the vault, non-transferable tokens cannot be paid out,
+/// and transfer hooks need accounts the program does not pass
+const UNSUPPORTED_MINT_EXTENSIONS: [ExtensionType; 3] = [
+    ExtensionType::PermanentDelegate,
+    ExtensionType::NonTransferable,
+    ExtensionType::TransferHook,
+];
+
+/// Reject stake mints with extensions token rounds cannot handle safely.
+/// Transfer fees are supported: deposits credit what the vault received.
+pub fn check_mint_extensions(mint: &AccountInfo) -> Result<()> {
+    if *mint.owner != anchor_spl::token_2022::ID {
+        return Ok(());
+    }
+
+    let data = mint.try_borrow_data()?;
+    let mint = StateWithExtensions::<SplMint>::unpack(&data)?;
+    for extension in mint.get_extension_types()? {
+        require!(
+            !UNSUPPORTED_MINT_EXTENSIONS.contains(&extension),
+            LeftRightError::UnsupportedMintExtension
+        );
+    }
+    Ok(())
+}
+
+/// Accounts a round's stake moves through
+pub struct Stake<'a, 'info> {
+    round_id: u64,
+    stake_mint: Option<Pubkey>,
+    vault: &'a SystemAccount<'info>,
+    vault_bump: u8,
+    mint: Option<&'a InterfaceAccount<'info, Mint>>,
+    vault_token: Option<&'a InterfaceAccount<'info, TokenAccount>>,
+    token_program: Option<&'a Interface<'info, TokenInterface>>,
+    system_program: &'a Program<'info, System>,
+}
+
+impl<'a, 'info> Stake<'a, 'info> {
+    pub fn new(
+        round: &Round,
+        vault: &'a SystemAccount<'info>,
+        vault_bump: u8,
+        mint: &'a Option<InterfaceAccount<'info, Mint>>,
+        vault_token: &'a Option<InterfaceAccount<'info, TokenAccount>>,
+        token_program: &'a Option<Interface<'info, TokenInterface>>,
+        system_program: &'a Program<'info, System>,
+    ) -> Self {
+        Self {
+            round_id: round.round_id,
+            stake_mint: round.stake_mint,
+            vault,
+            vault_bump,
+            mint: mint.as_ref(),
+            vault_token: vault_token.as_ref(),
+            token_program: token_program.as_ref(),
+            system_program,
+        }
+    }
+
+    pub fn is_token(&self) -> bool {
+        self.stake_mint.is_some()
+    }
+
+    /// Amount of stake held by the vault, read from the account data so it
+    /// reflects transfers made earlier in the instruction
+    pub fn vault_balance(&self) -> Result<u64> {
+        if self.is_token() {
+            let vault_token = self.vault_token()?.to_account_info();
+            let data = vault_token.try_borrow_data()?;
+            Ok(StateWithExtensions::<SplAccount>::unpack(&data)?.base.amount)
+        } else {
+            Ok(self.vault.lamports())
+        }
+    }
+
+    /// Move stake from a signing wallet to another wallet (SOL round), or
+    /// between their token accounts (token round)
+    pub fn transfer(
+        &self,
+        from: &AccountInfo<'info>,
+        from_token: Option<&InterfaceAccount<'info, TokenAccount>>,
+        to: &AccountInfo<'info>,
+        to_token: Option<&InterfaceAccount<'info, TokenAccount>>,
+        amount: u64,
+    ) -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+
+        if self.is_token() {
+            let from_token = self.token_account(from, from_token)?;
+            let to_token = self.token_account(to, to_token)?;
+            let mint = self.mint()?;
+            token_interface::transfer_checked(
+                CpiContext::new(
+                    self.token_program()?.to_account_info(),
+                    token_interface::TransferChecked {
+                        from: from_token.to_account_info(),
+                        mint: mint.to_account_info(),
+                        to: to_token.to_account_info(),
+                        authority: from.clone(),
+                    },
+                ),
+                amount,
+                mint.decimals,
+            )
+        } else {
+            system_program::transfer(
+                CpiContext::new(
+                    self.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: from.clone(),
+                        to: to.clone(),
+                    },
+                ),
+                amount,
+            )
+        }
+    }
+
+    /// Move stake from a signing wallet into the vault, returning the amount
+    /// the vault actually received (less than `amount` for Token-2022 mints
+    /// charging a transfer fee)
+    pub fn deposit(
+        &self,
+        from: &AccountInfo<'info>,
+        from_token: Option<&InterfaceAccount<'info, TokenAccount>>,
+        amount: u64,
+    ) -> Result<u64> {
+        let balance_before = self.vault_balance()?;
+        self.transfer(
+            from,
+            from_token,
+            &self.vault.to_account_info(),
+            self.vault_token,
+            amount,
+        )?;
+        self.vault_balance()?
+            .checked_sub(balance_before)
+            .ok_or_else(|| error!(LeftRightError::MathOverflow))
+    }
+
+    /// Pay stake out of the vault, signed by the vault PDA
+    pub fn pay_out(
+        &self,
+        to: &AccountInfo<'info>,
+        to_token: Option<&InterfaceAccount<'info, TokenAccount>>,
+        amount: u64,
+    ) -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+
+        let round_id_bytes = self.round_id.to_le_bytes();
+        let vault_seeds = &[
+            b"vault".as_ref(),
+            round_id_bytes.as_ref(),
+            &[self.vault_bump],
+        ];
+
+        if self.is_token() {
+            let to_token = self.token_account(to, to_token)?;
+            let mint = self.mint()?;
+            token_interface::transfer_checked(
+                CpiContext::new_with_signer(
+                    self.token_program()?.to_account_info(),
+                    token_interface::TransferChecked {
+                        from: self.vault_token()?.to_account_info(),
+                        mint: mint.to_account_info(),
+                        to: to_token.to_account_info(),
+                        authority: self.vault.to_account_info(),
+                    },
+                    &[vault_seeds],
+                ),
+                amount,
+                mint.decimals,
+            )
+        } else {
+            system_program::transfer(
+                CpiContext::new_with_signer(
+                    self.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: self.vault.to_account_info(),
+                        to: to.clone(),
+                    },
+                    &[vault_seeds],
+                ),
+                amount,
+            )
+        }
+    }
+
+    /// Close a token round's (emptied) vault token account, returning its
+    /// rent to `destination`. Transfer fees withheld in the account are
+    /// harvested to the mint first, as Token-2022 refuses to close it
+    /// otherwise.
+    pub fn close_vault_token(&self, destination: &AccountInfo<'info>) -> Result<()> {
+        let vault_token = self.vault_token()?.to_account_info();
+        let token_program = self.token_program()?.to_account_info();
+
+        if self.withheld_fees(&vault_token)? > 0 {
+            let mint = self.mint()?.to_account_info();
+            token_interface::harvest_withheld_tokens_to_mint(
+                CpiContext::new(
+                    token_program.clone(),
+                    HarvestWithheldTokensToMint {
+                        token_program_id: token_program.clone(),
+                        mint,
+                    },
+                ),
+                vec![vault_token.clone()],
+            )?;
+        }
+
+        let round_id_bytes = self.round_id.to_le_bytes();
+        let vault_seeds = &[
+            b"vault".as_ref(),
+            round_id_bytes.as_ref(),
+            &[self.vault_bump],
+        ];
+
+        token_interface::close_account(CpiContext::new_with_signer(
+            token_program,
+            token_interface::CloseAccount {
+                account: vault_token,
+                destination: destination.clone(),
+                authority: self.vault.to_account_info(),
+            },
+            &[vault_seeds],
+        ))
+    }
+
+    /// Transfer fees withheld in a Token-2022 account, waiting to be harvested
+    fn withheld_fees(&self, account: &AccountInfo) -> Result<u64> {
+        if *account.owner != anchor_spl::token_2022::ID {
+            return Ok(0);
+        }
+        let data = account.try_borrow_data()?;
+        let state = StateWithExtensions::<SplAccount>::unpack(&data)?;
+        Ok(state
+            .get_extension::<TransferFeeAmount>()
+            .map_or(0, |fee_amount| u64::from(fee_amount.withheld_amount)))
+    }
+
+    fn mint(&self) -> Result<&'a InterfaceAccount<'info, Mint>> {
+        self.mint
+            .ok_or_else(|| error!(LeftRightError::TokenAccountRequired))
+    }
+
+    fn vault_token(&self) -> Result<&'a InterfaceAccount<'info, TokenAccount>> {
+        self.vault_token
+            .ok_or_else(|| error!(LeftRightError::TokenAccountRequired))
+    }
+
+    fn token_program(&self) -> Result<&'a Interface<'info, TokenInterface>> {
+        self.token_program
+            .ok_or_else(|| error!(LeftRightError::TokenAccountRequired))
+    }
+
+    /// Token account of `wallet` in the round's stake mint
+    fn token_account<'b>(
+        &self,
+        wallet: &AccountInfo<'info>,
+        account: Option<&'b InterfaceAccount<'info, TokenAccount>>,
+    ) -> Result<&'b InterfaceAccount<'info, TokenAccount>> {
+        let account = account.ok_or(LeftRightError::TokenAccountRequired)?;
+        require!(
+            Some(account.mint) == self.stake_mint && account.owner == wallet.key(),
+            LeftRightError::InvalidTokenAccount
+        );
+        Ok(account)
+    }
+}
@@ -1,6 +1,8 @@
 use anchor_lang::prelude::*;
 use super::Side;
 
+/// A bettor's position in a round: one per (round, bettor), topped up by
+/// repeated bets on the same side
 #[account]
 #[derive(Default)]
 pub struct Bet {
@@ -10,18 +12,26 @@ pub struct Bet {
     pub bettor: Pubkey,
     /// Which side (LEFT=0, RIGHT=1)
     pub side: u8,
-    /// Amount in pool after fees (used for payout calculation)
+    /// Amount in pool after fees, summed over top-ups (used for payout calculation)
     pub amount: u64,
-    /// Original bet amount before fees (for display/reference)
+    /// Original bet amount before fees, summed over top-ups (for display/reference)
     pub original_amount: u64,
-    /// Unix timestamp when bet was placed
+    /// Platform fee sent to treasury (refundable on no-contest rounds)
+    pub treasury_fee: u64,
+    /// Part of the treasury fee due back on a refund the treasury did not
+    /// co-sign, until it pays it with `settle_fee_refund`
+    pub fee_owed: u64,
+    /// Unix timestamp when the position was opened
     pub bet_time: i64,
-    /// Weight multiplier (scaled by 100, e.g., 150 = 1.5x)
-    pub weight: u64,
-    /// Index of this bet in the round
+    /// Sum of each top-up's amount times its own timing weight, exactly as
+    /// added to the round's weighted pool
+    pub weighted_amount: u64,
+    /// Index of this position in the round (assigned on-chain, for ordering)
     pub bet_index: u32,
     /// Whether payout has been processed
     pub paid_out: bool,
+    /// Cashed out before betting closed; no payout or refund is owed
+    pub withdrawn: bool,
     /// Referrer who shared the blink (receives fee cut)
     pub referrer: Option<Pubkey>,
     /// Bump seed for PDA
@@ -35,41 +45,22 @@ impl Bet {
         1 +  // side
         8 +  // amount (pool contribution)
         8 +  // original_amount
+        8 +  // treasury_fee
+        8 +  // fee_owed
         8 +  // bet_time
-        8 +  // weight
+        8 +  // weighted_amount
         4 +  // bet_index
         1 +  // paid_out
+        1 +  // withdrawn
         1 + 32 + // referrer (Option<Pubkey>)
         1;   // bump
 
     pub const SEED: &'static [u8] = b"bet";
 
-    /// Weight multipliers scaled by 100 (e.g., 150 = 1.5x)
-    pub const WEIGHT_TIER_1: u64 = 150; // Hours 0-3: 1.5x
-    pub const WEIGHT_TIER_2: u64 = 130; // Hours 3-6: 1.3x
-    pub const WEIGHT_TIER_3: u64 = 115; // Hours 6-9: 1.15x
-    pub const WEIGHT_TIER_4: u64 = 100; // Hours 9-12: 1.0x
-
-    /// Calculate weight based on time elapsed since round start
-    pub fn calculate_weight(round_start: i64, bet_time: i64) -> u64 {
-        let elapsed_seconds = bet_time.saturating_sub(round_start);
-        let elapsed_hours = elapsed_seconds / 3600;
-
-        if elapsed_hours < 3 {
-            Self::WEIGHT_TIER_1
-        } else if elapsed_hours < 6 {
-            Self::WEIGHT_TIER_2
-        } else if elapsed_hours < 9 {
-            Self::WEIGHT_TIER_3
-        } else {
-            Self::WEIGHT_TIER_4
-        }
-    }
-
-    /// Calculate weighted amount for payout distribution
-    pub fn weighted_amount(&self) -> u64 {
+    /// Weighted amount of a single bet for payout distribution
+    pub fn weigh(amount: u64, weight: u64) -> u64 {
         // amount * weight / 100
-        (self.amount as u128 * self.weight as u128 / 100) as u64
+        (amount as u128 * weight as u128 / 100) as u64
     }
 
     pub fn side_enum(&self) -> Option<Side> {
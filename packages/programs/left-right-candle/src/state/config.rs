@@ -1,4 +1,17 @@
 use anchor_lang::prelude::*;
+use super::{TiePolicy, WeightCurve};
+
+/// Per-bet limits of a round, in its stake units: from `Config` for SOL
+/// rounds, from the mint's `MintConfig` for token rounds
+pub struct BetLimits {
+    pub min_bet: u64,
+    pub max_bet: u64,
+    /// 0 = no cap
+    pub max_round_exposure: u64,
+    /// Rolling 24h cap per wallet (0 = no cap)
+    pub max_daily_exposure: u64,
+    pub dynamic_max_bet_floor: u64,
+}
 
 #[account]
 #[derive(Default)]
@@ -9,14 +22,63 @@ pub struct Config {
     pub fee_bps: u16,
     /// Referrer share of fee in basis points (e.g., 100 = 1% of pool, or 40% of 2.5% fee)
     pub referrer_fee_bps: u16,
-    /// Minimum bet amount in lamports
+    /// Minimum bet amount in lamports (token rounds use their `MintConfig`)
     pub min_bet_lamports: u64,
-    /// Maximum bet amount in lamports
+    /// Maximum bet amount in lamports (token rounds use their `MintConfig`)
     pub max_bet_lamports: u64,
     /// Treasury wallet for collecting fees
     pub treasury: Pubkey,
     /// Counter for round IDs
     pub round_counter: u64,
+    /// Maximum age of an oracle price in seconds
+    pub max_price_age_secs: i64,
+    /// Maximum oracle confidence interval relative to price, in basis points
+    pub max_conf_bps: u16,
+    /// Keys allowed to sign off-chain price attestations
+    pub price_signers: Vec<Pubkey>,
+    /// Number of distinct price signers required to accept an attestation
+    pub signer_threshold: u8,
+    /// Challenge window for proposed end prices in seconds (0 = settle immediately)
+    pub dispute_window_secs: i64,
+    /// Bond the proposer (and any disputer) must post, in lamports
+    pub settlement_bond_lamports: u64,
+    /// Minimum number of samples a TWAP round must average over
+    pub twap_min_samples: u8,
+    /// Minimum time between two recorded price samples in seconds
+    pub twap_min_spacing_secs: i64,
+    /// Tie rule applied to rounds started from now on
+    pub tie_policy: TiePolicy,
+    /// Lamports rolled over from tied rounds, waiting in the vault of
+    /// round `round_counter` to become that round's bonus pool
+    pub rollover_pool: u64,
+    /// Whether refunds also return the treasury fee (on payouts the treasury co-signs)
+    pub refund_fees: bool,
+    /// Time after a round's end before anyone can void it if still unsettled
+    pub void_grace_secs: i64,
+    /// Shortest betting window a round can be started with (seconds)
+    pub min_betting_secs: i64,
+    /// Longest betting window a round can be started with (seconds)
+    pub max_betting_secs: i64,
+    /// Shortest wait between betting close and settlement (seconds)
+    pub min_waiting_secs: i64,
+    /// Longest wait between betting close and settlement (seconds)
+    pub max_waiting_secs: i64,
+    /// Penalty on withdrawing a bet before betting closes, in basis points
+    pub exit_penalty_bps: u16,
+    /// Send exit penalties to the treasury instead of the round's winners
+    pub exit_penalty_to_treasury: bool,
+    /// Early-bird weight curve applied to rounds started from now on
+    pub weight_curve: WeightCurve,
+    /// Most a wallet can bet in one round, before fees (0 = no cap)
+    pub max_round_exposure_lamports: u64,
+    /// Most a wallet can bet in SOL rounds over any rolling 24 hours, before
+    /// fees (0 = no cap)
+    pub max_daily_exposure_lamports: u64,
+    /// Limit each bet to this share of the round's current pool, in basis
+    /// points (0 = rule disabled)
+    pub dynamic_max_bet_bps: u16,
+    /// Bet size always allowed under the dynamic rule, however small the pool
+    pub dynamic_max_bet_floor_lamports: u64,
     /// Bump seed for PDA
     pub bump: u8,
 }
@@ -30,10 +92,79 @@ impl Config {
         8 +  // max_bet_lamports
         32 + // treasury
         8 +  // round_counter
+        8 +  // max_price_age_secs
+        2 +  // max_conf_bps
+        4 + 32 * Self::MAX_PRICE_SIGNERS + // price_signers
+        1 +  // signer_threshold
+        8 +  // dispute_window_secs
+        8 +  // settlement_bond_lamports
+        1 +  // twap_min_samples
+        8 +  // twap_min_spacing_secs
+        1 +  // tie_policy (enum)
+        8 +  // rollover_pool
+        1 +  // refund_fees
+        8 +  // void_grace_secs
+        8 +  // min_betting_secs
+        8 +  // max_betting_secs
+        8 +  // min_waiting_secs
+        8 +  // max_waiting_secs
+        2 +  // exit_penalty_bps
+        1 +  // exit_penalty_to_treasury
+        WeightCurve::LEN + // weight_curve
+        8 +  // max_round_exposure_lamports
+        8 +  // max_daily_exposure_lamports
+        2 +  // dynamic_max_bet_bps
+        8 +  // dynamic_max_bet_floor_lamports
         1;   // bump
 
     pub const SEED: &'static [u8] = b"config";
 
+    /// Default oracle staleness limit (60 seconds)
+    pub const DEFAULT_MAX_PRICE_AGE_SECS: i64 = 60;
+
+    /// Default oracle confidence limit (2% of price)
+    pub const DEFAULT_MAX_CONF_BPS: u16 = 200;
+
+    /// Default minimum TWAP sample count
+    pub const DEFAULT_TWAP_MIN_SAMPLES: u8 = 4;
+
+    /// Default minimum spacing between TWAP samples (5 minutes)
+    pub const DEFAULT_TWAP_MIN_SPACING_SECS: i64 = 5 * 60;
+
+    /// Default grace period before an unsettled round can be voided (48 hours)
+    pub const DEFAULT_VOID_GRACE_SECS: i64 = 48 * 60 * 60;
+
+    /// Default round duration bounds: 15 minutes to 7 days for each phase
+    pub const DEFAULT_MIN_DURATION_SECS: i64 = 15 * 60;
+    pub const DEFAULT_MAX_DURATION_SECS: i64 = 7 * 24 * 60 * 60;
+
+    /// Default penalty on early withdrawals (5%)
+    pub const DEFAULT_EXIT_PENALTY_BPS: u16 = 500;
+
+    /// Maximum number of price signers
+    pub const MAX_PRICE_SIGNERS: usize = 5;
+
+    /// Bet limits of SOL rounds
+    pub fn bet_limits(&self) -> BetLimits {
+        BetLimits {
+            min_bet: self.min_bet_lamports,
+            max_bet: self.max_bet_lamports,
+            max_round_exposure: self.max_round_exposure_lamports,
+            max_daily_exposure: self.max_daily_exposure_lamports,
+            dynamic_max_bet_floor: self.dynamic_max_bet_floor_lamports,
+        }
+    }
+
+    /// Largest bet allowed into a round holding `total_pool`:
+    /// max(floor, dynamic_max_bet_bps of the pool), or no limit when disabled
+    pub fn dynamic_max_bet(&self, total_pool: u64, floor: u64) -> u64 {
+        if self.dynamic_max_bet_bps == 0 {
+            return u64::MAX;
+        }
+        let pool_share = (total_pool as u128 * self.dynamic_max_bet_bps as u128 / 10000) as u64;
+        pool_share.max(floor)
+    }
+
     /// Calculate fee split: returns (treasury_fee, referrer_fee)
     pub fn calculate_fee_split(&self, total_pool: u64, has_referrer: bool) -> (u64, u64) {
         let total_fee = (total_pool as u128)
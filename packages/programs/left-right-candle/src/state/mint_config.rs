@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+use super::BetLimits;
+
+/// Bet limits for rounds staked in an SPL token, in the mint's base units.
+/// Rounds can only be started with a stake mint that has one.
+#[account]
+#[derive(Default)]
+pub struct MintConfig {
+    /// Stake mint these limits apply to
+    pub mint: Pubkey,
+    /// Minimum bet amount
+    pub min_bet: u64,
+    /// Maximum bet amount
+    pub max_bet: u64,
+    /// Most a wallet can bet in one round, before fees (0 = no cap)
+    pub max_round_exposure: u64,
+    /// Most a wallet can bet in this mint over any rolling 24 hours, before
+    /// fees (0 = no cap)
+    pub max_daily_exposure: u64,
+    /// Bet size always allowed under the dynamic pool rule
+    pub dynamic_max_bet_floor: u64,
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl MintConfig {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // mint
+        8 +  // min_bet
+        8 +  // max_bet
+        8 +  // max_round_exposure
+        8 +  // max_daily_exposure
+        8 +  // dynamic_max_bet_floor
+        1;   // bump
+
+    pub const SEED: &'static [u8] = b"mint_config";
+
+    pub fn bet_limits(&self) -> BetLimits {
+        BetLimits {
+            min_bet: self.min_bet,
+            max_bet: self.max_bet,
+            max_round_exposure: self.max_round_exposure,
+            max_daily_exposure: self.max_daily_exposure,
+            dynamic_max_bet_floor: self.dynamic_max_bet_floor,
+        }
+    }
+}
@@ -1,7 +1,19 @@
 pub mod bet;
 pub mod config;
+pub mod mint_config;
+pub mod payout_root;
+pub mod price;
+pub mod price_samples;
 pub mod round;
+pub mod user_stats;
+pub mod weight_curve;
 
 pub use bet::*;
 pub use config::*;
+pub use mint_config::*;
+pub use payout_root::*;
+pub use price::*;
+pub use price_samples::*;
 pub use round::*;
+pub use user_stats::*;
+pub use weight_curve::*;
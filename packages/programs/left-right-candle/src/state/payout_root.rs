@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+
+/// Payouts of a round settled by merkle root: the root of one
+/// (index, bettor, payout) leaf per winning bet, computed off-chain from the
+/// round's `Bet` accounts. Winners claim with proofs until the claim
+/// deadline; the bitmap records which leaves have been claimed.
+#[account]
+#[derive(Default)]
+pub struct PayoutRoot {
+    /// Round these payouts belong to
+    pub round_id: u64,
+    /// Root of the payout merkle tree (see `crate::merkle`)
+    pub merkle_root: [u8; 32],
+    /// Sum of all payouts in the tree
+    pub total_payout: u64,
+    /// Sum of the payouts claimed so far
+    pub claimed_amount: u64,
+    /// Number of leaves in the tree
+    pub leaf_count: u32,
+    /// Number of leaves claimed so far
+    pub claimed_count: u32,
+    /// Unix timestamp after which unclaimed payouts are forfeited and the
+    /// round can be finalized
+    pub claim_deadline: i64,
+    /// Bump seed for PDA
+    pub bump: u8,
+    /// One bit per leaf, set once claimed
+    pub claimed: Vec<u8>,
+}
+
+impl PayoutRoot {
+    pub const SEED: &'static [u8] = b"payout_root";
+
+    /// Most leaves a tree can have (keeps the bitmap at 8 KB)
+    pub const MAX_LEAVES: u32 = 65_536;
+
+    /// Account size for a tree of `leaf_count` leaves
+    pub fn space(leaf_count: u32) -> usize {
+        8 +  // discriminator
+        8 +  // round_id
+        32 + // merkle_root
+        8 +  // total_payout
+        8 +  // claimed_amount
+        4 +  // leaf_count
+        4 +  // claimed_count
+        8 +  // claim_deadline
+        1 +  // bump
+        4 + Self::bitmap_len(leaf_count) // claimed
+    }
+
+    pub fn bitmap_len(leaf_count: u32) -> usize {
+        (leaf_count as usize).div_ceil(8)
+    }
+
+    pub fn is_claimed(&self, index: u32) -> bool {
+        self.claimed[index as usize / 8] & (1 << (index % 8)) != 0
+    }
+
+    pub fn set_claimed(&mut self, index: u32) {
+        self.claimed[index as usize / 8] |= 1 << (index % 8);
+    }
+}
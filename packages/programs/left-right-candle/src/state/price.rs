@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+use std::cmp::Ordering;
+
+use crate::errors::LeftRightError;
+
+/// Oracle price with its scale, as published by the feed
+///
+/// The real value is `price * 10^expo` (e.g. price = 250_000_000, expo = -8
+/// is $2.50). Exponents differ per feed, so never compare mantissas directly.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Price {
+    /// Price mantissa
+    pub price: i64,
+    /// Confidence interval, same scale as `price`
+    pub conf: u64,
+    /// Price exponent
+    pub expo: i32,
+    /// Unix timestamp the price was published at
+    pub publish_time: i64,
+}
+
+impl Price {
+    pub const LEN: usize = 8 + // price
+        8 + // conf
+        4 + // expo
+        8;  // publish_time
+
+    /// Compare the real values of two prices, rescaling to the smaller exponent
+    pub fn cmp_value(&self, other: &Price) -> Result<Ordering> {
+        let expo = self.expo.min(other.expo);
+        let lhs = self.price_at(expo)?;
+        let rhs = other.price_at(expo)?;
+        Ok(lhs.cmp(&rhs))
+    }
+
+    /// Price mantissa re-expressed with a smaller (or equal) exponent
+    pub fn price_at(&self, expo: i32) -> Result<i128> {
+        Self::rescale(self.price as i128, self.expo, expo)
+    }
+
+    /// Confidence re-expressed with a smaller (or equal) exponent
+    pub fn conf_at(&self, expo: i32) -> Result<i128> {
+        Self::rescale(self.conf as i128, self.expo, expo)
+    }
+
+    fn rescale(value: i128, from_expo: i32, to_expo: i32) -> Result<i128> {
+        require!(to_expo <= from_expo, LeftRightError::MathOverflow);
+        let factor = 10i128
+            .checked_pow((from_expo - to_expo) as u32)
+            .ok_or(LeftRightError::MathOverflow)?;
+        Ok(value
+            .checked_mul(factor)
+            .ok_or(LeftRightError::MathOverflow)?)
+    }
+}
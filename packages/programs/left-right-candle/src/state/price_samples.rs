@@ -0,0 +1,84 @@
+use anchor_lang::prelude::*;
+use super::Price;
+use crate::errors::LeftRightError;
+
+/// Oracle samples published during a round's waiting period (between
+/// `betting_end_time` and `end_time`), used to settle TWAP rounds. Keeps the
+/// most recent `MAX_SAMPLES` samples, oldest first.
+#[account]
+#[derive(Default)]
+pub struct PriceSamples {
+    /// Round these samples belong to
+    pub round_id: u64,
+    /// Cranker who paid for the account, refunded when it is closed
+    pub payer: Pubkey,
+    /// Recorded samples, ordered by publish time
+    pub samples: Vec<Price>,
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl PriceSamples {
+    pub const MAX_SAMPLES: usize = 32;
+
+    pub const LEN: usize = 8 + // discriminator
+        8 +  // round_id
+        32 + // payer
+        4 + Price::LEN * Self::MAX_SAMPLES + // samples
+        1;   // bump
+
+    pub const SEED: &'static [u8] = b"samples";
+
+    pub fn last(&self) -> Option<&Price> {
+        self.samples.last()
+    }
+
+    /// Append a sample, dropping the oldest one when full
+    pub fn push(&mut self, sample: Price) {
+        if self.samples.len() >= Self::MAX_SAMPLES {
+            self.samples.remove(0);
+        }
+        self.samples.push(sample);
+    }
+
+    /// Time-weighted average of the last `n` samples
+    ///
+    /// Each sample's price holds until the next sample is published, so the
+    /// average covers the window from the first to the last of the `n`
+    /// samples. The result carries the last sample's publish time.
+    pub fn twap(&self, n: usize) -> Result<Price> {
+        require!(n >= 2 && self.samples.len() >= n, LeftRightError::NotEnoughSamples);
+
+        let window = &self.samples[self.samples.len() - n..];
+        let first = window[0];
+        let last = window[n - 1];
+
+        let mut weighted_price: i128 = 0;
+        let mut weighted_conf: u128 = 0;
+        for pair in window.windows(2) {
+            require!(pair[0].expo == last.expo, LeftRightError::InvalidPriceFeed);
+            let duration = pair[1].publish_time
+                .checked_sub(pair[0].publish_time)
+                .ok_or(LeftRightError::MathOverflow)?;
+
+            weighted_price = weighted_price
+                .checked_add((pair[0].price as i128) * (duration as i128))
+                .ok_or(LeftRightError::MathOverflow)?;
+            weighted_conf = weighted_conf
+                .checked_add((pair[0].conf as u128) * (duration as u128))
+                .ok_or(LeftRightError::MathOverflow)?;
+        }
+
+        let total_duration = last.publish_time
+            .checked_sub(first.publish_time)
+            .ok_or(LeftRightError::MathOverflow)?;
+        require!(total_duration > 0, LeftRightError::NotEnoughSamples);
+
+        Ok(Price {
+            price: (weighted_price / total_duration as i128) as i64,
+            conf: (weighted_conf / total_duration as u128) as u64,
+            expo: last.expo,
+            publish_time: last.publish_time,
+        })
+    }
+}
@@ -1,8 +1,12 @@
 use anchor_lang::prelude::*;
+use super::{Bet, Price, WeightCurve};
+use crate::errors::LeftRightError;
+use crate::oracle::MAX_PRICE_SOURCES;
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default)]
 pub enum RoundStatus {
     /// Round is accepting bets
+    #[default]
     Open,
     /// Betting closed, waiting for settlement
     Locked,
@@ -10,12 +14,16 @@ pub enum RoundStatus {
     Settling,
     /// Round complete, all payouts processed
     Settled,
-}
-
-impl Default for RoundStatus {
-    fn default() -> Self {
-        RoundStatus::Open
-    }
+    /// End price proposed, waiting out the challenge window
+    Proposed,
+    /// Proposed end price disputed, waiting for resolution
+    Disputed,
+    /// No contest (one side empty), refunded tie or voided round, every bet is refunded
+    Refunding,
+    /// Aborted by the admin before settlement, bets refunded in full
+    Cancelled,
+    /// Payouts posted as a merkle root, winners claim with proofs
+    Claiming,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
@@ -38,6 +46,30 @@ impl TryFrom<u8> for Side {
     }
 }
 
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PriceSource {
+    /// Pyth price feed account
+    #[default]
+    Pyth,
+    /// Ed25519 attestations from the configured price signers
+    Attestation,
+    /// Switchboard aggregator account
+    Switchboard,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TiePolicy {
+    /// Every bettor gets their pool contribution back
+    #[default]
+    Refund,
+    /// RIGHT wins when the end price equals the start price
+    RightWins,
+    /// LEFT wins when the end price equals the start price
+    LeftWins,
+    /// Whole pool is added to the winnings of the next round
+    Rollover,
+}
+
 #[account]
 #[derive(Default)]
 pub struct Round {
@@ -45,32 +77,66 @@ pub struct Round {
     pub round_id: u64,
     /// Asset symbol (e.g., "WIF", "BONK", "SOL", "BTC")
     pub asset_symbol: String,
-    /// Start price (scaled integer, e.g., price * 1e8)
-    pub start_price: i64,
+    /// Sources combined (median) for start and end price
+    pub price_sources: Vec<PriceSource>,
+    /// Minimum number of fresh source readings needed to price the round
+    pub oracle_quorum: u8,
+    /// Pyth price feed used for both start and end price (if Pyth is a source)
+    pub price_feed: Pubkey,
+    /// Pyth feed id `price_feed` held at start, required of every later read
+    pub price_feed_id: [u8; 32],
+    /// Switchboard aggregator used for both start and end price (if Switchboard is a source)
+    pub switchboard_feed: Pubkey,
+    /// Settle on the TWAP of the last N recorded samples (0 = spot price)
+    pub twap_samples: u8,
+    /// What happens when the end price equals the start price (from config at start)
+    pub tie_policy: TiePolicy,
+    /// Early-bird weight curve for bets (from config at start)
+    pub weight_curve: WeightCurve,
+    /// SPL mint bets are staked in (`None` = SOL)
+    pub stake_mint: Option<Pubkey>,
+    /// Start price as read from the feed
+    pub start_price: Price,
     /// End price (populated at settlement)
-    pub end_price: i64,
+    pub end_price: Price,
+    /// Individual source readings behind `start_price`, indexed by `PriceSource`
+    pub start_readings: [Option<Price>; MAX_PRICE_SOURCES],
+    /// Individual source readings behind `end_price`, indexed by `PriceSource`
+    pub end_readings: [Option<Price>; MAX_PRICE_SOURCES],
     /// Unix timestamp when round started
     pub start_time: i64,
-    /// Unix timestamp when betting ends (12 hours after start)
+    /// Unix timestamp when betting ends (start + betting duration)
     pub betting_end_time: i64,
-    /// Unix timestamp when round settles (24 hours after start)
+    /// Unix timestamp when round settles (betting end + waiting duration)
     pub end_time: i64,
     /// Current round status
     pub status: RoundStatus,
-    /// Total lamports bet on LEFT side
+    /// Total bet on LEFT side (lamports, or stake mint units)
     pub left_pool: u64,
-    /// Total lamports bet on RIGHT side
+    /// Total bet on RIGHT side (lamports, or stake mint units)
     pub right_pool: u64,
     /// Weighted total for LEFT side (for payout calculation)
     pub left_weighted_pool: u64,
     /// Weighted total for RIGHT side (for payout calculation)
     pub right_weighted_pool: u64,
+    /// Pool rolled over from tied rounds, paid to this round's winners
+    pub bonus_pool: u64,
     /// Number of bets placed
     pub bet_count: u32,
     /// Number of payouts processed
     pub payouts_processed: u32,
-    /// Winning side (set at settlement)
+    /// Number of bet accounts closed
+    pub bets_closed: u32,
+    /// Winning side (set at settlement, `None` on a refunded or rolled-over tie)
     pub winning_side: Option<Side>,
+    /// Who proposed the end price (optimistic settlement)
+    pub proposer: Pubkey,
+    /// Unix timestamp after which an undisputed proposal can be finalized
+    pub challenge_end_time: i64,
+    /// Who disputed the proposed end price, if anyone
+    pub disputer: Option<Pubkey>,
+    /// Bond posted by the proposer, and matched by a disputer (lamports)
+    pub settlement_bond: u64,
     /// Bump seed for PDA
     pub bump: u8,
 }
@@ -79,8 +145,19 @@ impl Round {
     pub const LEN: usize = 8 +  // discriminator
         8 +   // round_id
         4 + 10 + // asset_symbol (String with max 10 chars)
-        8 +   // start_price
-        8 +   // end_price
+        4 + MAX_PRICE_SOURCES + // price_sources
+        1 +   // oracle_quorum
+        32 +  // price_feed
+        32 +  // price_feed_id
+        32 +  // switchboard_feed
+        1 +   // twap_samples
+        1 +   // tie_policy (enum)
+        WeightCurve::LEN + // weight_curve
+        1 + 32 + // stake_mint (Option<Pubkey>)
+        Price::LEN + // start_price
+        Price::LEN + // end_price
+        (1 + Price::LEN) * MAX_PRICE_SOURCES + // start_readings
+        (1 + Price::LEN) * MAX_PRICE_SOURCES + // end_readings
         8 +   // start_time
         8 +   // betting_end_time
         8 +   // end_time
@@ -89,21 +166,27 @@ impl Round {
         8 +   // right_pool
         8 +   // left_weighted_pool
         8 +   // right_weighted_pool
+        8 +   // bonus_pool
         4 +   // bet_count
         4 +   // payouts_processed
+        4 +   // bets_closed
         1 + 1 + // winning_side (Option<Side>)
+        32 +  // proposer
+        8 +   // challenge_end_time
+        1 + 32 + // disputer (Option<Pubkey>)
+        8 +   // settlement_bond
         1;    // bump
 
     pub const SEED: &'static [u8] = b"round";
 
-    /// Duration of betting window in seconds (12 hours)
-    pub const BETTING_DURATION: i64 = 12 * 60 * 60;
+    /// Seed of the token account holding a token round's bets
+    pub const TOKEN_VAULT_SEED: &'static [u8] = b"token_vault";
 
-    /// Duration of waiting period after betting closes (12 hours)
-    pub const WAITING_DURATION: i64 = 12 * 60 * 60;
+    /// Seed of the PDA holding settlement bonds
+    pub const BOND_SEED: &'static [u8] = b"bond";
 
-    /// Total round duration (24 hours)
-    pub const ROUND_DURATION: i64 = Self::BETTING_DURATION + Self::WAITING_DURATION;
+    /// Outcome code reported in events for a tie without a winner
+    pub const OUTCOME_TIE: u8 = 2;
 
     pub fn total_pool(&self) -> u64 {
         self.left_pool.saturating_add(self.right_pool)
@@ -113,12 +196,201 @@ impl Round {
         self.left_weighted_pool.saturating_add(self.right_weighted_pool)
     }
 
+    /// Length of the betting window in seconds
+    pub fn betting_duration(&self) -> i64 {
+        self.betting_end_time.saturating_sub(self.start_time)
+    }
+
     pub fn is_betting_open(&self, current_time: i64) -> bool {
         self.status == RoundStatus::Open &&
         current_time < self.betting_end_time
     }
 
+    /// Every bet gets its stake back: no contest, a refunded tie, or a
+    /// cancelled round
+    pub fn is_refunding(&self) -> bool {
+        self.status == RoundStatus::Refunding || self.status == RoundStatus::Cancelled
+    }
+
+    /// Every bet has been paid out or refunded, nothing more is owed
+    pub fn is_finished(&self) -> bool {
+        match self.status {
+            RoundStatus::Settled => true,
+            RoundStatus::Cancelled => self.payouts_processed >= self.bet_count,
+            _ => false,
+        }
+    }
+
     pub fn is_ready_to_settle(&self, current_time: i64) -> bool {
         current_time >= self.end_time
     }
+
+    /// Winning side for a given end price, `None` when the tie policy
+    /// leaves no winner (refund or rollover)
+    pub fn winning_side_for(&self, end_price: &Price) -> Result<Option<Side>> {
+        Ok(match end_price.cmp_value(&self.start_price)? {
+            std::cmp::Ordering::Less => Some(Side::Left),
+            std::cmp::Ordering::Greater => Some(Side::Right),
+            std::cmp::Ordering::Equal => match self.tie_policy {
+                TiePolicy::RightWins => Some(Side::Right),
+                TiePolicy::LeftWins => Some(Side::Left),
+                TiePolicy::Refund | TiePolicy::Rollover => None,
+            },
+        })
+    }
+
+    /// Outcome code for events: LEFT=0, RIGHT=1, tie=2
+    pub fn outcome(&self) -> u8 {
+        self.winning_side.map_or(Self::OUTCOME_TIE, |side| side as u8)
+    }
+
+    /// Pool paid out to the winning side
+    pub fn winning_pool(&self) -> u64 {
+        self.winning_side.map_or(0, |side| self.side_pool(side))
+    }
+
+    /// Funds a settled round hands to the next round: the whole pool on a
+    /// rolled-over tie, and any bonus a refunded tie leaves unclaimed
+    pub fn rollover_amount(&self) -> u64 {
+        match (self.winning_side, self.tie_policy) {
+            (Some(_), _) => 0,
+            (None, TiePolicy::Rollover) => self.total_pool().saturating_add(self.bonus_pool),
+            (None, _) => self.bonus_pool,
+        }
+    }
+
+    /// Status to pay out from once the outcome is final
+    pub fn payout_status(&self) -> RoundStatus {
+        match (self.winning_side, self.tie_policy) {
+            (None, TiePolicy::Refund) => RoundStatus::Refunding,
+            _ => RoundStatus::Settling,
+        }
+    }
+
+    /// Amount owed to a bet once the outcome is final (`Settling`,
+    /// `Refunding` or `Cancelled`): a winner's stake plus their weighted share of the
+    /// losers' pool and bonus, or a refunded stake. Losers, withdrawn
+    /// positions and rolled-over ties are owed nothing.
+    pub fn payout_for(&self, bet: &Bet) -> Result<u64> {
+        let bet_side = bet.side_enum().ok_or(LeftRightError::InvalidSide)?;
+
+        // Note: Referrer fees were already paid at bet time, nothing to do here
+
+        Ok(match self.winning_side {
+            // Cashed out before betting closed, nothing owed
+            _ if bet.withdrawn => 0,
+            // No contest or refunded tie: pool contribution back
+            _ if self.is_refunding() => bet.amount,
+            Some(winning_side) if bet_side == winning_side => {
+                // Get pools based on winning side
+                let (winning_weighted_pool, losing_pool) = match winning_side {
+                    Side::Left => (self.left_weighted_pool, self.right_pool),
+                    Side::Right => (self.right_weighted_pool, self.left_pool),
+                };
+
+                // Prize is the losers' pool plus any bonus rolled over from ties
+                let prize_pool = losing_pool
+                    .checked_add(self.bonus_pool)
+                    .ok_or(LeftRightError::MathOverflow)?;
+
+                // Step 1: Winner gets their bet back
+                let mut payout = bet.amount;
+
+                // Step 2: Add weighted share of the prize pool (if there is one)
+                if prize_pool > 0 && winning_weighted_pool > 0 {
+                    // share = (bet.weighted_amount / winning_weighted_pool) * prize_pool,
+                    // with the position's weighted amount summed over top-ups
+                    let bonus = (bet.weighted_amount as u128)
+                        .checked_mul(prize_pool as u128)
+                        .and_then(|v| v.checked_div(winning_weighted_pool as u128))
+                        .ok_or(LeftRightError::MathOverflow)? as u64;
+
+                    payout = payout
+                        .checked_add(bonus)
+                        .ok_or(LeftRightError::MathOverflow)?;
+                }
+
+                payout
+            }
+            // Losing side, or a rolled-over tie (pool moved to the next round)
+            _ => 0,
+        })
+    }
+
+    /// Treasury fee returned along with a refund, when fee refunds are enabled
+    pub fn fee_refund_for(&self, bet: &Bet, refund_fees: bool) -> u64 {
+        if self.is_refunding() && refund_fees && !bet.withdrawn {
+            bet.treasury_fee
+        } else {
+            0
+        }
+    }
+
+    /// Count a processed payout; the round is settled once every bet has
+    /// one (a cancelled round stays cancelled, see `is_finished`)
+    pub fn record_payout(&mut self) -> Result<()> {
+        self.payouts_processed = self.payouts_processed
+            .checked_add(1)
+            .ok_or(LeftRightError::MathOverflow)?;
+
+        if self.payouts_processed >= self.bet_count && self.status != RoundStatus::Cancelled {
+            self.status = RoundStatus::Settled;
+        }
+        Ok(())
+    }
+
+    /// A round where one side has no bets has no contest to settle
+    pub fn is_one_sided(&self) -> bool {
+        self.left_pool == 0 || self.right_pool == 0
+    }
+
+    pub fn has_price_source(&self, source: PriceSource) -> bool {
+        self.price_sources.contains(&source)
+    }
+
+    pub fn side_pool(&self, side: Side) -> u64 {
+        match side {
+            Side::Left => self.left_pool,
+            Side::Right => self.right_pool,
+        }
+    }
+
+    /// Implied payout per lamport staked of a bet already added to the pools,
+    /// if `side` wins, in basis points (25_000 = 2.5x). `stake` is the amount
+    /// before fees; `pool_amount` and `weighted_amount` are what the bet
+    /// added to the pools.
+    pub fn bet_multiplier_bps(
+        &self,
+        side: Side,
+        stake: u64,
+        pool_amount: u64,
+        weighted_amount: u64,
+    ) -> u64 {
+        if stake == 0 {
+            return 0;
+        }
+        let (side_weighted_pool, other_pool) = match side {
+            Side::Left => (self.left_weighted_pool, self.right_pool),
+            Side::Right => (self.right_weighted_pool, self.left_pool),
+        };
+        let prize_pool = other_pool.saturating_add(self.bonus_pool) as u128;
+        let share = if side_weighted_pool > 0 {
+            weighted_amount as u128 * prize_pool / side_weighted_pool as u128
+        } else {
+            0
+        };
+        let payout = pool_amount as u128 + share;
+        (payout * 10000 / stake as u128).min(u64::MAX as u128) as u64
+    }
+
+    /// Unweighted payout per lamport staked on `side` if it wins, in basis
+    /// points (25_000 = 2.5x). Zero while the side has no bets.
+    pub fn implied_odds_bps(&self, side: Side) -> u64 {
+        let side_pool = self.side_pool(side);
+        if side_pool == 0 {
+            return 0;
+        }
+        let payout_pool = self.total_pool().saturating_add(self.bonus_pool);
+        (payout_pool as u128 * 10000 / side_pool as u128).min(u64::MAX as u128) as u64
+    }
 }
@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+
+/// Per-wallet betting volume in one stake (SOL, or a stake mint), used to
+/// enforce the rolling 24h exposure cap. Volume is bucketed by hour;
+/// `hourly_volume[h % 24]` holds hour `h`.
+#[account]
+#[derive(Default)]
+pub struct UserStats {
+    /// Wallet these stats belong to
+    pub user: Pubkey,
+    /// Amount bet (before fees) per hour, ring buffer over the last 24 hours
+    pub hourly_volume: [u64; 24],
+    /// Most recent hour (unix time / 3600) with recorded volume
+    pub last_hour: i64,
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl UserStats {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // user
+        8 * Self::HOURS + // hourly_volume
+        8 +  // last_hour
+        1;   // bump
+
+    /// Seeds: [SEED, user] for SOL, [SEED, user, stake mint] for a mint
+    pub const SEED: &'static [u8] = b"user_stats";
+
+    /// Width of the rolling window, in hourly buckets
+    pub const HOURS: usize = 24;
+
+    /// Clear buckets for hours that have dropped out of the window since the
+    /// last update
+    fn advance(&mut self, hour: i64) {
+        if hour <= self.last_hour {
+            return;
+        }
+        let elapsed = (hour - self.last_hour).min(Self::HOURS as i64);
+        for h in (hour - elapsed + 1)..=hour {
+            self.hourly_volume[h.rem_euclid(Self::HOURS as i64) as usize] = 0;
+        }
+        self.last_hour = hour;
+    }
+
+    /// Volume bet over the last 24 hours, including the current one
+    pub fn rolling_volume(&mut self, current_time: i64) -> u64 {
+        self.advance(current_time.div_euclid(3600));
+        self.hourly_volume
+            .iter()
+            .fold(0u64, |total, volume| total.saturating_add(*volume))
+    }
+
+    /// Record volume in the current hour
+    pub fn record(&mut self, current_time: i64, amount: u64) {
+        let hour = current_time.div_euclid(3600);
+        self.advance(hour);
+        let bucket = &mut self.hourly_volume[hour.rem_euclid(Self::HOURS as i64) as usize];
+        *bucket = bucket.saturating_add(amount);
+    }
+}
@@ -0,0 +1,110 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::LeftRightError;
+
+/// One breakpoint of a stepped weight curve
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq)]
+pub struct WeightStep {
+    /// Point in the betting window the step starts at, in basis points
+    /// of the window (2500 = a quarter in)
+    pub start_bps: u16,
+    /// Weight multiplier from that point on (scaled by 100, e.g., 150 = 1.5x)
+    pub weight: u64,
+}
+
+impl WeightStep {
+    pub const LEN: usize = 2 + // start_bps
+        8;  // weight
+}
+
+/// Early-bird bonus: weight of a bet by how far into the betting window it lands
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub enum WeightCurve {
+    /// Weight of the last step started at the bet's point in the window
+    Steps(Vec<WeightStep>),
+    /// Linear decay from `max_weight` at window open to 1.0x at window close
+    Linear { max_weight: u64 },
+}
+
+impl Default for WeightCurve {
+    /// Quarter tiers: 1.5x, 1.3x, 1.15x, 1.0x (3h tiers on a 12h window)
+    fn default() -> Self {
+        WeightCurve::Steps(vec![
+            WeightStep { start_bps: 0, weight: 150 },
+            WeightStep { start_bps: 2500, weight: 130 },
+            WeightStep { start_bps: 5000, weight: 115 },
+            WeightStep { start_bps: 7500, weight: 100 },
+        ])
+    }
+}
+
+impl WeightCurve {
+    /// Maximum number of steps in a stepped curve
+    pub const MAX_STEPS: usize = 8;
+
+    pub const LEN: usize = 1 + // variant
+        4 + WeightStep::LEN * Self::MAX_STEPS; // largest variant (Steps)
+
+    /// Weight of 1.0x (weights are scaled by 100)
+    pub const BASE_WEIGHT: u64 = 100;
+
+    /// Largest weight a curve may use (10x)
+    pub const MAX_WEIGHT: u64 = 1000;
+
+    pub fn validate(&self) -> Result<()> {
+        match self {
+            WeightCurve::Steps(steps) => {
+                require!(
+                    !steps.is_empty() && steps.len() <= Self::MAX_STEPS,
+                    LeftRightError::InvalidWeightCurve
+                );
+                // Must cover the window from its start, in increasing order
+                require!(steps[0].start_bps == 0, LeftRightError::InvalidWeightCurve);
+                for pair in steps.windows(2) {
+                    require!(
+                        pair[0].start_bps < pair[1].start_bps,
+                        LeftRightError::InvalidWeightCurve
+                    );
+                }
+                for step in steps {
+                    require!(
+                        step.start_bps < 10000
+                            && step.weight > 0
+                            && step.weight <= Self::MAX_WEIGHT,
+                        LeftRightError::InvalidWeightCurve
+                    );
+                }
+            }
+            WeightCurve::Linear { max_weight } => {
+                require!(
+                    *max_weight >= Self::BASE_WEIGHT && *max_weight <= Self::MAX_WEIGHT,
+                    LeftRightError::InvalidWeightCurve
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Weight of a bet placed at `bet_time` in a betting window opening at
+    /// `round_start` and lasting `betting_duration` seconds
+    pub fn weight_at(&self, round_start: i64, betting_duration: i64, bet_time: i64) -> u64 {
+        let duration = betting_duration.max(1) as i128;
+        let elapsed = (bet_time.saturating_sub(round_start) as i128).clamp(0, duration);
+
+        match self {
+            WeightCurve::Steps(steps) => {
+                let elapsed_bps = elapsed * 10000 / duration;
+                steps
+                    .iter()
+                    .take_while(|step| step.start_bps as i128 <= elapsed_bps)
+                    .last()
+                    .map_or(Self::BASE_WEIGHT, |step| step.weight)
+            }
+            WeightCurve::Linear { max_weight } => {
+                let bonus = max_weight.saturating_sub(Self::BASE_WEIGHT) as i128;
+                let remaining = bonus - bonus * elapsed / duration;
+                Self::BASE_WEIGHT + remaining as u64
+            }
+        }
+    }
+}
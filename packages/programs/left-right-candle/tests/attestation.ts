@@ -0,0 +1,49 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Ed25519Program, Keypair, PublicKey } from "@solana/web3.js";
+
+const ATTESTATION_DOMAIN = Buffer.from("left-right-candle:price:v1");
+
+export interface AttestedPrice {
+  price: anchor.BN;
+  conf: anchor.BN;
+  expo: number;
+  publishTime: anchor.BN;
+}
+
+// Mirrors PriceAttestation::message in src/oracle/attestation.rs
+export function attestationMessage(
+  programId: PublicKey,
+  roundId: anchor.BN,
+  kind: "start" | "end",
+  assetSymbol: string,
+  price: AttestedPrice
+): Buffer {
+  const symbol = Buffer.from(assetSymbol, "utf-8");
+  const symbolLen = Buffer.alloc(4);
+  symbolLen.writeUInt32LE(symbol.length);
+  const expo = Buffer.alloc(4);
+  expo.writeInt32LE(price.expo);
+
+  return Buffer.concat([
+    ATTESTATION_DOMAIN,
+    programId.toBuffer(),
+    roundId.toArrayLike(Buffer, "le", 8),
+    Buffer.from([kind === "start" ? 0 : 1]),
+    symbolLen,
+    symbol,
+    price.price.toTwos(64).toArrayLike(Buffer, "le", 8),
+    price.conf.toArrayLike(Buffer, "le", 8),
+    expo,
+    price.publishTime.toTwos(64).toArrayLike(Buffer, "le", 8),
+  ]);
+}
+
+// One Ed25519 verify instruction per signer, to precede the program instruction
+export function attestationInstructions(signers: Keypair[], message: Buffer) {
+  return signers.map((signer) =>
+    Ed25519Program.createInstructionWithPrivateKey({
+      privateKey: signer.secretKey,
+      message,
+    })
+  );
+}
@@ -3,6 +3,23 @@ import { Program } from "@coral-xyz/anchor";
 import { PublicKey, SystemProgram, LAMPORTS_PER_SOL } from "@solana/web3.js";
 import { expect } from "chai";
 import { LeftRightCandle } from "../target/types/left_right_candle";
+import {
+  MockPrice,
+  createMockAggregator,
+  createMockPriceFeed,
+  setMockPrice,
+} from "./mock-oracle";
+import { attestationInstructions, attestationMessage } from "./attestation";
+import { merkleTree, payoutLeaf } from "./merkle";
+import {
+  TOKEN_2022_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+  TRANSFER_FEE_ACCOUNT_LEN,
+  createMint,
+  createTokenAccount,
+  mintTo,
+  tokenBalance,
+} from "./spl-token";
 
 describe("left-right-candle", () => {
   const provider = anchor.AnchorProvider.env();
@@ -18,8 +35,180 @@ describe("left-right-candle", () => {
 
   // Test constants
   const FEE_BPS = 250; // 2.5%
+  const REFERRER_FEE_BPS = 100; // 1%
   const MIN_BET = LAMPORTS_PER_SOL / 100; // 0.01 SOL
   const MAX_BET = LAMPORTS_PER_SOL * 10; // 10 SOL
+  const BETTING_SECS = new anchor.BN(12 * 60 * 60); // 12h betting window
+  const WAITING_SECS = new anchor.BN(12 * 60 * 60); // 12h until settlement
+  // Token accounts left out of SOL rounds
+  const SOL_ROUND = { stakeMint: null, mintConfig: null, vaultToken: null, tokenProgram: null };
+  // Token accounts left out of SOL bets and payouts
+  const NO_TOKENS = { stakeMint: null, vaultToken: null, treasuryToken: null, tokenProgram: null };
+  const START_PRICE: MockPrice = { price: 250_000_000n, conf: 100_000n, expo: -8 };
+  // Shortest round the lowered duration bounds allow (see useShortRounds)
+  const SHORT_BETTING_SECS = new anchor.BN(4);
+  const SHORT_WAITING_SECS = new anchor.BN(1);
+  // Config::DEFAULT_VOID_GRACE_SECS
+  const DEFAULT_VOID_GRACE_SECS = 48 * 60 * 60;
+
+  const sleep = (ms: number) => new Promise((resolve) => setTimeout(resolve, ms));
+
+  const expectError = async (tx: Promise<unknown>, code: string) => {
+    try {
+      await tx;
+      expect.fail(`expected ${code}`);
+    } catch (err: any) {
+      expect(err.error.errorCode.code).to.equal(code);
+    }
+  };
+
+  const roundAddresses = (roundId: anchor.BN) => {
+    const roundIdBytes = roundId.toArrayLike(Buffer, "le", 8);
+    const [roundPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("round"), roundIdBytes],
+      program.programId
+    );
+    const [vaultPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), roundIdBytes],
+      program.programId
+    );
+    return { roundId, roundIdBytes, roundPda, vaultPda };
+  };
+
+  const betAddress = (roundIdBytes: Buffer, bettor: PublicKey) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("bet"), roundIdBytes, bettor.toBuffer()],
+      program.programId
+    )[0];
+
+  // Let rounds run for seconds, so tests can wait for them to end
+  const useShortRounds = async () => {
+    await program.methods
+      .setDurationBounds(
+        SHORT_BETTING_SECS,
+        new anchor.BN(7 * 24 * 60 * 60),
+        new anchor.BN(0),
+        new anchor.BN(7 * 24 * 60 * 60)
+      )
+      .accounts({ admin: admin.publicKey })
+      .rpc();
+  };
+
+  // Config's default weight curve (see WeightCurve::default)
+  const DEFAULT_WEIGHT_CURVE = {
+    steps: {
+      0: [
+        { startBps: 0, weight: new anchor.BN(150) },
+        { startBps: 2500, weight: new anchor.BN(130) },
+        { startBps: 5000, weight: new anchor.BN(115) },
+        { startBps: 7500, weight: new anchor.BN(100) },
+      ],
+    },
+  };
+
+  const setWeightCurve = (weightCurve: any) =>
+    program.methods.setWeightCurve(weightCurve).accounts({ admin: admin.publicKey }).rpc();
+
+  const setVoidGracePeriod = (graceSecs: number) =>
+    program.methods
+      .setVoidGracePeriod(new anchor.BN(graceSecs))
+      .accounts({ admin: admin.publicKey })
+      .rpc();
+
+  // Start a SOL round priced by a fresh mock Pyth feed (settling at the TWAP
+  // of its last `twapSamples` samples, if any)
+  const startPythRound = async (
+    bettingSecs: anchor.BN = SHORT_BETTING_SECS,
+    waitingSecs: anchor.BN = SHORT_WAITING_SECS,
+    twapSamples = 0,
+    startPrice: MockPrice = START_PRICE
+  ) => {
+    const priceFeed = await createMockPriceFeed(provider, startPrice);
+    const config = await program.account.config.fetch(configPda);
+    await program.methods
+      .startRound("WIF", [{ pyth: {} }], 1, null, twapSamples, bettingSecs, waitingSecs)
+      .accounts({ admin: admin.publicKey, priceFeed, switchboardFeed: null, ...SOL_ROUND })
+      .rpc();
+    return { ...roundAddresses(config.roundCounter), priceFeed };
+  };
+
+  // Fresh wallet with some SOL to bet
+  const newBettor = async (sol = 2) => {
+    const bettor = anchor.web3.Keypair.generate();
+    const signature = await provider.connection.requestAirdrop(
+      bettor.publicKey,
+      sol * LAMPORTS_PER_SOL
+    );
+    await provider.connection.confirmTransaction(signature, "confirmed");
+    return bettor;
+  };
+
+  const placeBet = (
+    roundPda: PublicKey,
+    bettor: anchor.web3.Keypair,
+    side: number,
+    amount: number,
+    minMultiplierBps: anchor.BN | null = null
+  ) =>
+    program.methods
+      .placeBet(side, new anchor.BN(amount), minMultiplierBps)
+      .accounts({
+        round: roundPda,
+        treasury: treasuryKeypair.publicKey,
+        bettor: bettor.publicKey,
+        mintUserStats: null,
+        referrer: null,
+        mintConfig: null,
+        bettorToken: null,
+        referrerToken: null,
+        ...NO_TOKENS,
+      })
+      .signers([bettor])
+      .rpc();
+
+  // Wait for a round to end, then settle it at `endPrice` on its own feed
+  // (the settler posts the bond when a dispute window is configured)
+  const settleRound = async (
+    round: { roundPda: PublicKey; priceFeed: PublicKey },
+    endPrice: MockPrice,
+    settler: anchor.web3.Keypair | null = null
+  ) => {
+    const { endTime } = await program.account.round.fetch(round.roundPda);
+    const waitMs = (endTime.toNumber() + 2) * 1000 - Date.now();
+    if (waitMs > 0) await sleep(waitMs);
+    await setMockPrice(provider, round.priceFeed, endPrice);
+    await program.methods
+      .settleRound(null)
+      .accounts({
+        round: round.roundPda,
+        priceFeed: round.priceFeed,
+        switchboardFeed: null,
+        priceSamples: null,
+        settler: settler?.publicKey ?? admin.publicKey,
+      })
+      .signers(settler ? [settler] : [])
+      .rpc();
+  };
+
+  // What a RIGHT bet is owed once RIGHT has won (see Round::payout_for)
+  const rightWinnerPayout = async (roundPda: PublicKey, betPda: PublicKey) => {
+    const { leftPool, rightWeightedPool, bonusPool } = await program.account.round.fetch(roundPda);
+    const bet = await program.account.bet.fetch(betPda);
+    return bet.amount.add(bet.weightedAmount.mul(leftPool.add(bonusPool)).div(rightWeightedPool));
+  };
+
+  const processPayout = (roundPda: PublicKey, betPda: PublicKey, bettor: PublicKey) =>
+    program.methods
+      .processPayout()
+      .accounts({
+        round: roundPda,
+        bet: betPda,
+        bettor,
+        treasury: null,
+        bettorToken: null,
+        ...NO_TOKENS,
+      })
+      .rpc();
 
   before(async () => {
     // Find config PDA
@@ -35,7 +224,7 @@ describe("left-right-candle", () => {
   describe("initialize", () => {
     it("initializes the config", async () => {
       const tx = await program.methods
-        .initialize(FEE_BPS, new anchor.BN(MIN_BET), new anchor.BN(MAX_BET))
+        .initialize(FEE_BPS, REFERRER_FEE_BPS, new anchor.BN(MIN_BET), new anchor.BN(MAX_BET))
         .accounts({
           config: configPda,
           admin: admin.publicKey,
@@ -54,6 +243,8 @@ describe("left-right-candle", () => {
       expect(config.maxBetLamports.toNumber()).to.equal(MAX_BET);
       expect(config.treasury.toString()).to.equal(treasuryKeypair.publicKey.toString());
       expect(config.roundCounter.toNumber()).to.equal(0);
+      expect(config.tiePolicy).to.deep.equal({ refund: {} });
+      expect(config.voidGraceSecs.toNumber()).to.equal(48 * 60 * 60);
     });
 
     it("rejects invalid fee bps", async () => {
@@ -62,41 +253,1809 @@ describe("left-right-candle", () => {
     });
   });
 
-  // Note: Full integration tests require a mock Pyth price feed
-  // These tests demonstrate the structure for localnet testing
+  describe("set_tie_policy", () => {
+    it("updates the tie policy", async () => {
+      await program.methods
+        .setTiePolicy({ rollover: {} })
+        .accounts({ admin: admin.publicKey })
+        .rpc();
+
+      let config = await program.account.config.fetch(configPda);
+      expect(config.tiePolicy).to.deep.equal({ rollover: {} });
+
+      await program.methods
+        .setTiePolicy({ refund: {} })
+        .accounts({ admin: admin.publicKey })
+        .rpc();
+
+      config = await program.account.config.fetch(configPda);
+      expect(config.tiePolicy).to.deep.equal({ refund: {} });
+    });
+
+    it("rejects non-admin", async () => {
+      const stranger = anchor.web3.Keypair.generate();
+      try {
+        await program.methods
+          .setTiePolicy({ rightWins: {} })
+          .accounts({ admin: stranger.publicKey })
+          .signers([stranger])
+          .rpc();
+        expect.fail("expected Unauthorized");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("Unauthorized");
+      }
+    });
+  });
+
+  describe("set_weight_curve", () => {
+    after(async () => {
+      await setWeightCurve(DEFAULT_WEIGHT_CURVE);
+    });
+
+    it("sets a linear curve", async () => {
+      await setWeightCurve({ linear: { maxWeight: new anchor.BN(200) } });
+
+      const config = await program.account.config.fetch(configPda);
+      expect(config.weightCurve.linear.maxWeight.toNumber()).to.equal(200);
+    });
+
+    it("rejects steps that don't start at the window open", async () => {
+      try {
+        await program.methods
+          .setWeightCurve({
+            steps: { 0: [{ startBps: 1000, weight: new anchor.BN(150) }] },
+          })
+          .accounts({ admin: admin.publicKey })
+          .rpc();
+        expect.fail("expected InvalidWeightCurve");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("InvalidWeightCurve");
+      }
+    });
+  });
+
+  // Price feeds are served by the mock-oracle program (see ./mock-oracle.ts)
 
   describe("start_round", () => {
     it("should start a new round with valid asset", async () => {
-      // This test requires a mock Pyth price feed account
-      // In production tests, use pyth-sdk-solana test utilities
-      console.log("Skipping: requires mock Pyth price feed");
+      const { roundId, roundPda, priceFeed } = await startPythRound(BETTING_SECS, WAITING_SECS);
+      expect(roundId.toNumber()).to.equal(0);
+
+      const round = await program.account.round.fetch(roundPda);
+      expect(round.assetSymbol).to.equal("WIF");
+      expect(round.priceFeed.toString()).to.equal(priceFeed.toString());
+      expect(round.startPrice.price.toNumber()).to.equal(250_000_000);
+      expect(round.startPrice.expo).to.equal(-8);
+      expect(round.startPrice.conf.toNumber()).to.equal(100_000);
+    });
+
+    it("should start a 1h round with proportional timestamps", async () => {
+      const HALF_HOUR = new anchor.BN(30 * 60);
+      const { roundPda } = await startPythRound(HALF_HOUR, HALF_HOUR);
+
+      const round = await program.account.round.fetch(roundPda);
+      expect(round.bettingEndTime.sub(round.startTime).toNumber()).to.equal(30 * 60);
+      expect(round.endTime.sub(round.bettingEndTime).toNumber()).to.equal(30 * 60);
+    });
+
+    it("should reject a duration outside the config bounds", async () => {
+      await expectError(startPythRound(new anchor.BN(60), WAITING_SECS), "InvalidRoundDuration");
+    });
+
+    it("should reject a stale price", async () => {
+      // A stale reading is dropped, leaving no source to price the round
+      await expectError(
+        startPythRound(BETTING_SECS, WAITING_SECS, 0, {
+          ...START_PRICE,
+          publishTime: Math.floor(Date.now() / 1000) - 3600,
+        }),
+        "OracleQuorumNotMet"
+      );
+    });
+
+    it("should reject a price published in the future", async () => {
+      // Dropped like a stale reading (PriceFromFuture)
+      await expectError(
+        startPythRound(BETTING_SECS, WAITING_SECS, 0, {
+          ...START_PRICE,
+          publishTime: Math.floor(Date.now() / 1000) + 3600,
+        }),
+        "OracleQuorumNotMet"
+      );
+    });
+
+    it("should reject a wide confidence interval", async () => {
+      await expectError(
+        startPythRound(BETTING_SECS, WAITING_SECS, 0, {
+          ...START_PRICE,
+          conf: 25_000_000n, // 10% of price
+        }),
+        "OracleQuorumNotMet"
+      );
+    });
+
+    it("should start a round from a signed price attestation", async () => {
+      const signers = [anchor.web3.Keypair.generate(), anchor.web3.Keypair.generate()];
+      await program.methods
+        .setPriceSigners(signers.map((s) => s.publicKey), 2)
+        .accounts({ admin: admin.publicKey })
+        .rpc();
+
+      const config = await program.account.config.fetch(configPda);
+      const price = {
+        price: new anchor.BN(180_000),
+        conf: new anchor.BN(100),
+        expo: -8,
+        publishTime: new anchor.BN(Math.floor(Date.now() / 1000)),
+      };
+      const message = attestationMessage(
+        program.programId,
+        config.roundCounter,
+        "start",
+        "BONK",
+        price
+      );
+
+      await program.methods
+        .startRound("BONK", [{ attestation: {} }], 1, price, 0, BETTING_SECS, WAITING_SECS)
+        .accounts({ admin: admin.publicKey, priceFeed: null, switchboardFeed: null, ...SOL_ROUND })
+        .preInstructions(attestationInstructions(signers, message))
+        .rpc();
+
+      const { roundPda } = roundAddresses(config.roundCounter);
+      const round = await program.account.round.fetch(roundPda);
+      expect(round.startPrice.price.toNumber()).to.equal(180_000);
+      expect(round.priceSources).to.deep.equal([{ attestation: {} }]);
+    });
+
+    it("should start a round at the median of Pyth and Switchboard", async () => {
+      const priceFeed = await createMockPriceFeed(provider, START_PRICE);
+      // Same asset at a different scale: $2.60 with 6 decimals
+      const switchboardFeed = await createMockAggregator(provider, {
+        price: 2_600_000n,
+        conf: 1_000n,
+        expo: -6,
+      });
+
+      const config = await program.account.config.fetch(configPda);
+      await program.methods
+        .startRound("WIF", [{ pyth: {} }, { switchboard: {} }], 2, null, 0, BETTING_SECS, WAITING_SECS)
+        .accounts({ admin: admin.publicKey, priceFeed, switchboardFeed, ...SOL_ROUND })
+        .rpc();
+
+      const { roundPda } = roundAddresses(config.roundCounter);
+      const round = await program.account.round.fetch(roundPda);
+      // Median of $2.50 and $2.60 at the smaller exponent
+      expect(round.startPrice.expo).to.equal(-8);
+      expect(round.startPrice.price.toNumber()).to.equal(255_000_000);
+      expect(round.startReadings[0].price.toNumber()).to.equal(250_000_000);
+      expect(round.startReadings[2].price.toNumber()).to.equal(2_600_000);
+    });
+  });
+
+  describe("cancel_round", () => {
+    const cancelRound = (roundPda: PublicKey) =>
+      program.methods
+        .cancelRound()
+        .accounts({ admin: admin.publicKey, round: roundPda })
+        .rpc();
+
+    it("should cancel an open round", async () => {
+      const { roundPda } = await startPythRound(BETTING_SECS, WAITING_SECS);
+      await cancelRound(roundPda);
+
+      const round = await program.account.round.fetch(roundPda);
+      expect(round.status).to.deep.equal({ cancelled: {} });
+
+      await expectError(cancelRound(roundPda), "RoundNotCancellable");
+    });
+
+    it("should close a cancelled round with no bets", async () => {
+      const { roundPda } = await startPythRound(BETTING_SECS, WAITING_SECS);
+      await cancelRound(roundPda);
+
+      await program.methods
+        .sweepVault()
+        .accounts({
+          round: roundPda,
+          treasury: treasuryKeypair.publicKey,
+          ...NO_TOKENS,
+        })
+        .rpc();
+      await program.methods
+        .closeRound()
+        .accounts({ admin: admin.publicKey, round: roundPda })
+        .rpc();
+
+      expect(await provider.connection.getAccountInfo(roundPda)).to.be.null;
+    });
+
+    describe("refund_bet", () => {
+      const STAKE = LAMPORTS_PER_SOL / 10;
+
+      const setRefundFees = (refundFees: boolean) =>
+        program.methods
+          .setRefundFees(refundFees)
+          .accounts({ admin: admin.publicKey })
+          .rpc();
+
+      const refundBet = (
+        roundPda: PublicKey,
+        betPda: PublicKey,
+        bettor: PublicKey,
+        treasury: anchor.web3.Keypair | null
+      ) =>
+        program.methods
+          .refundBet()
+          .accounts({
+            round: roundPda,
+            bet: betPda,
+            bettor,
+            treasury: treasury?.publicKey ?? null,
+            bettorToken: null,
+            ...NO_TOKENS,
+          })
+          .signers(treasury ? [treasury] : [])
+          .rpc();
+
+      before(async () => {
+        await setRefundFees(true);
+      });
+
+      after(async () => {
+        await setRefundFees(false);
+      });
+
+      it("should return the whole bet and close it when the treasury co-signs", async () => {
+        const round = await startPythRound(BETTING_SECS, WAITING_SECS);
+        const bettor = await newBettor();
+        await placeBet(round.roundPda, bettor, 0, STAKE);
+        await cancelRound(round.roundPda);
+
+        const betPda = betAddress(round.roundIdBytes, bettor.publicKey);
+        const betRent = (await provider.connection.getAccountInfo(betPda)).lamports;
+        const before = await provider.connection.getBalance(bettor.publicKey);
+        await refundBet(round.roundPda, betPda, bettor.publicKey, treasuryKeypair);
+        const after = await provider.connection.getBalance(bettor.publicKey);
+
+        expect(after - before).to.equal(STAKE + betRent);
+        expect(await provider.connection.getAccountInfo(betPda)).to.be.null;
+        const { betsClosed } = await program.account.round.fetch(round.roundPda);
+        expect(betsClosed).to.equal(1);
+      });
+
+      it("should keep a bet open until the treasury settles the fee it owes", async () => {
+        const round = await startPythRound(BETTING_SECS, WAITING_SECS);
+        const bettor = await newBettor();
+        await placeBet(round.roundPda, bettor, 0, STAKE);
+        await cancelRound(round.roundPda);
+
+        const betPda = betAddress(round.roundIdBytes, bettor.publicKey);
+        const before = await provider.connection.getBalance(bettor.publicKey);
+        await refundBet(round.roundPda, betPda, bettor.publicKey, null);
+        const afterRefund = await provider.connection.getBalance(bettor.publicKey);
+
+        let bet = await program.account.bet.fetch(betPda);
+        expect(afterRefund - before).to.equal(bet.amount.toNumber());
+        expect(bet.paidOut).to.be.true;
+        expect(bet.feeOwed.toString()).to.equal(bet.treasuryFee.toString());
+
+        const closeBet = () =>
+          program.methods
+            .closeBet()
+            .accounts({ round: round.roundPda, bet: betPda, bettor: bettor.publicKey })
+            .rpc();
+        try {
+          await closeBet();
+          expect.fail("expected FeeRefundOwed");
+        } catch (err: any) {
+          expect(err.error.errorCode.code).to.equal("FeeRefundOwed");
+        }
+
+        const settleFeeRefund = () =>
+          program.methods
+            .settleFeeRefund()
+            .accounts({
+              round: round.roundPda,
+              bet: betPda,
+              bettor: bettor.publicKey,
+              treasury: treasuryKeypair.publicKey,
+              bettorToken: null,
+              ...NO_TOKENS,
+            })
+            .signers([treasuryKeypair])
+            .rpc();
+        await settleFeeRefund();
+        const afterSettle = await provider.connection.getBalance(bettor.publicKey);
+        expect(afterSettle - afterRefund).to.equal(bet.treasuryFee.toNumber());
+        expect(afterSettle - before).to.equal(STAKE);
+
+        bet = await program.account.bet.fetch(betPda);
+        expect(bet.feeOwed.toNumber()).to.equal(0);
+        try {
+          await settleFeeRefund();
+          expect.fail("expected NoFeeRefundOwed");
+        } catch (err: any) {
+          expect(err.error.errorCode.code).to.equal("NoFeeRefundOwed");
+        }
+
+        await closeBet();
+        expect(await provider.connection.getAccountInfo(betPda)).to.be.null;
+        const { betsClosed } = await program.account.round.fetch(round.roundPda);
+        expect(betsClosed).to.equal(1);
+      });
+    });
+  });
+
+  describe("lock_round", () => {
+    it("should reject locking during the betting window", async () => {
+      const { roundPda } = await startPythRound(BETTING_SECS, WAITING_SECS);
+      await expectError(
+        program.methods.lockRound().accounts({ round: roundPda }).rpc(),
+        "BettingPeriodActive"
+      );
+    });
+
+    it("should lock a round after betting closes and report its implied odds", async () => {
+      await useShortRounds();
+      const round = await startPythRound();
+      await placeBet(round.roundPda, await newBettor(), 0, LAMPORTS_PER_SOL / 10);
+      await placeBet(round.roundPda, await newBettor(), 1, (3 * LAMPORTS_PER_SOL) / 10);
+
+      const { bettingEndTime } = await program.account.round.fetch(round.roundPda);
+      await sleep((bettingEndTime.toNumber() + 1) * 1000 - Date.now());
+
+      let listener: number;
+      const locked = new Promise<any>((resolve) => {
+        listener = program.addEventListener("roundLocked", resolve);
+      });
+      await program.methods.lockRound().accounts({ round: round.roundPda }).rpc();
+      const event = await locked;
+      await program.removeEventListener(listener);
+
+      const state = await program.account.round.fetch(round.roundPda);
+      expect(state.status).to.deep.equal({ locked: {} });
+
+      // Whole pool (and bonus) paid per unit bet on each side, in bps
+      const payoutPool = state.leftPool.add(state.rightPool).add(state.bonusPool).muln(10_000);
+      expect(event.roundId.toString()).to.equal(state.roundId.toString());
+      expect(event.leftPool.toString()).to.equal(state.leftPool.toString());
+      expect(event.rightPool.toString()).to.equal(state.rightPool.toString());
+      expect(event.leftOddsBps.toString()).to.equal(payoutPool.div(state.leftPool).toString());
+      expect(event.rightOddsBps.toString()).to.equal(payoutPool.div(state.rightPool).toString());
+      expect(event.leftOddsBps.toNumber()).to.be.greaterThan(event.rightOddsBps.toNumber());
+    }).timeout(30_000);
+  });
+
+  describe("token rounds", () => {
+    const STAKE_DECIMALS = 6;
+    const MIN_STAKE = new anchor.BN(1_000_000); // 1 token
+    const MAX_STAKE = new anchor.BN(1_000_000_000); // 1000 tokens
+
+    const mintConfigAddress = (mint: PublicKey) =>
+      PublicKey.findProgramAddressSync(
+        [Buffer.from("mint_config"), mint.toBuffer()],
+        program.programId
+      )[0];
+
+    // Enable the mint for token rounds, optionally capping each wallet's
+    // rolling 24h volume in it
+    const enableMint = (stakeMint: PublicKey, maxDailyExposure = new anchor.BN(0)) =>
+      program.methods
+        .setMintConfig(MIN_STAKE, MAX_STAKE, new anchor.BN(0), maxDailyExposure, MIN_STAKE)
+        .accounts({ admin: admin.publicKey, mint: stakeMint })
+        .rpc();
+
+    // Start a round staked in the (enabled) mint
+    const startTokenRound = async (stakeMint: PublicKey, tokenProgram: PublicKey) => {
+      const priceFeed = await createMockPriceFeed(provider, START_PRICE);
+      const config = await program.account.config.fetch(configPda);
+      const { roundIdBytes, roundPda, vaultPda } = roundAddresses(config.roundCounter);
+      const [vaultToken] = PublicKey.findProgramAddressSync(
+        [Buffer.from("token_vault"), roundIdBytes],
+        program.programId
+      );
+
+      await program.methods
+        .startRound("WIF", [{ pyth: {} }], 1, null, 0, BETTING_SECS, WAITING_SECS)
+        .accounts({
+          admin: admin.publicKey,
+          priceFeed,
+          switchboardFeed: null,
+          stakeMint,
+          mintConfig: mintConfigAddress(stakeMint),
+          vaultToken,
+          tokenProgram,
+        })
+        .rpc();
+
+      return { roundPda, vaultPda, vaultToken };
+    };
+
+    // Admin and treasury token accounts of the mint, the admin's funded
+    const tokenAccounts = async (
+      stakeMint: PublicKey,
+      tokenProgram: PublicKey,
+      tokenAccountLen: number | undefined
+    ) => {
+      const bettorToken = await createTokenAccount(
+        provider, stakeMint, admin.publicKey, tokenProgram, tokenAccountLen
+      );
+      const treasuryToken = await createTokenAccount(
+        provider, stakeMint, treasuryKeypair.publicKey, tokenProgram, tokenAccountLen
+      );
+      await mintTo(provider, stakeMint, bettorToken, 10_000_000n, tokenProgram);
+      return { bettorToken, treasuryToken };
+    };
+
+    // Bet `stake` on LEFT from the admin's token account
+    const betTokens = (
+      round: { roundPda: PublicKey; vaultPda: PublicKey; vaultToken: PublicKey },
+      stakeMint: PublicKey,
+      tokenProgram: PublicKey,
+      accounts: { bettorToken: PublicKey; treasuryToken: PublicKey },
+      stake: bigint
+    ) =>
+      program.methods
+        .placeBet(0, new anchor.BN(stake.toString()), null)
+        .accounts({
+          round: round.roundPda,
+          vault: round.vaultPda,
+          treasury: treasuryKeypair.publicKey,
+          bettor: admin.publicKey,
+          userStats: null,
+          referrer: null,
+          mintConfig: mintConfigAddress(stakeMint),
+          stakeMint,
+          vaultToken: round.vaultToken,
+          ...accounts,
+          referrerToken: null,
+          tokenProgram,
+        })
+        .rpc();
+
+    // Enable the mint, start a round staked in it and bet `stake` on LEFT
+    const betInTokenRound = async (
+      stakeMint: PublicKey,
+      tokenProgram: PublicKey,
+      tokenAccountLen: number | undefined,
+      stake: bigint
+    ) => {
+      await enableMint(stakeMint);
+      const round = await startTokenRound(stakeMint, tokenProgram);
+      const accounts = await tokenAccounts(stakeMint, tokenProgram, tokenAccountLen);
+      await betTokens(round, stakeMint, tokenProgram, accounts, stake);
+
+      return { mintConfig: mintConfigAddress(stakeMint), ...round, ...accounts };
+    };
+
+    it("should set bet limits for a stake mint", async () => {
+      const stakeMint = await createMint(provider, STAKE_DECIMALS);
+      await program.methods
+        .setMintConfig(MIN_STAKE, MAX_STAKE, new anchor.BN(0), new anchor.BN(0), MIN_STAKE)
+        .accounts({ admin: admin.publicKey, mint: stakeMint })
+        .rpc();
+
+      const mintConfig = await program.account.mintConfig.fetch(mintConfigAddress(stakeMint));
+      expect(mintConfig.mint.toString()).to.equal(stakeMint.toString());
+      expect(mintConfig.minBet.toNumber()).to.equal(MIN_STAKE.toNumber());
+      expect(mintConfig.maxBet.toNumber()).to.equal(MAX_STAKE.toNumber());
+    });
+
+    it("should take bets and fees in the stake mint", async () => {
+      const stakeMint = await createMint(provider, STAKE_DECIMALS);
+      const stake = 2_000_000n;
+      const { roundPda, vaultToken, bettorToken, treasuryToken } = await betInTokenRound(
+        stakeMint, TOKEN_PROGRAM_ID, undefined, stake
+      );
+
+      const round = await program.account.round.fetch(roundPda);
+      expect(round.stakeMint.toString()).to.equal(stakeMint.toString());
+
+      const fee = (stake * BigInt(FEE_BPS)) / 10_000n;
+      expect(await tokenBalance(provider, treasuryToken)).to.equal(fee);
+      expect(await tokenBalance(provider, vaultToken)).to.equal(stake - fee);
+      expect(await tokenBalance(provider, bettorToken)).to.equal(10_000_000n - stake);
+      expect(round.leftPool.toString()).to.equal((stake - fee).toString());
+    });
+
+    it("should credit the pool net of a Token-2022 transfer fee", async () => {
+      const TRANSFER_FEE_BPS = 100n; // 1%
+      const stakeMint = await createMint(provider, STAKE_DECIMALS, {
+        transferFeeBps: Number(TRANSFER_FEE_BPS),
+      });
+      const stake = 2_000_000n;
+      const { roundPda, vaultToken } = await betInTokenRound(
+        stakeMint, TOKEN_2022_PROGRAM_ID, TRANSFER_FEE_ACCOUNT_LEN, stake
+      );
+
+      const deposit = stake - (stake * BigInt(FEE_BPS)) / 10_000n;
+      const received = deposit - (deposit * TRANSFER_FEE_BPS) / 10_000n;
+      expect(await tokenBalance(provider, vaultToken)).to.equal(received);
+
+      const round = await program.account.round.fetch(roundPda);
+      expect(round.leftPool.toString()).to.equal(received.toString());
+    });
+
+    it("should cap a wallet's daily volume in the stake mint's own units", async () => {
+      const setSolDailyCap = (daily: number) =>
+        program.methods
+          .setExposureLimits(new anchor.BN(0), new anchor.BN(daily))
+          .accounts({ admin: admin.publicKey })
+          .rpc();
+
+      const stakeMint = await createMint(provider, STAKE_DECIMALS);
+      await enableMint(stakeMint, new anchor.BN(3_000_000)); // 3 tokens a day
+      const accounts = await tokenAccounts(stakeMint, TOKEN_PROGRAM_ID, undefined);
+      const first = await startTokenRound(stakeMint, TOKEN_PROGRAM_ID);
+      const second = await startTokenRound(stakeMint, TOKEN_PROGRAM_ID);
+
+      // The lamport cap on SOL rounds leaves token bets alone
+      await setSolDailyCap(MIN_BET);
+      try {
+        await betTokens(first, stakeMint, TOKEN_PROGRAM_ID, accounts, 2_000_000n);
+        try {
+          await betTokens(second, stakeMint, TOKEN_PROGRAM_ID, accounts, 2_000_000n);
+          expect.fail("expected DailyExposureExceeded");
+        } catch (err: any) {
+          expect(err.error.errorCode.code).to.equal("DailyExposureExceeded");
+        }
+        await betTokens(second, stakeMint, TOKEN_PROGRAM_ID, accounts, 1_000_000n);
+      } finally {
+        await setSolDailyCap(0);
+      }
+
+      const [mintUserStats] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user_stats"), admin.publicKey.toBuffer(), stakeMint.toBuffer()],
+        program.programId
+      );
+      const stats = await program.account.userStats.fetch(mintUserStats);
+      const volume = stats.hourlyVolume.reduce((total, v) => total.add(v), new anchor.BN(0));
+      expect(volume.toNumber()).to.equal(3_000_000);
+    });
+
+    it("should reject a non-transferable mint", async () => {
+      const stakeMint = await createMint(provider, STAKE_DECIMALS, { nonTransferable: true });
+      try {
+        await program.methods
+          .setMintConfig(MIN_STAKE, MAX_STAKE, new anchor.BN(0), new anchor.BN(0), MIN_STAKE)
+          .accounts({ admin: admin.publicKey, mint: stakeMint })
+          .rpc();
+        expect.fail("expected UnsupportedMintExtension");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("UnsupportedMintExtension");
+      }
     });
   });
 
   describe("place_bet", () => {
+    const STAKE = LAMPORTS_PER_SOL / 10;
+    // Long enough to place several bets before betting closes
+    const OPEN_SECS = new anchor.BN(60);
+
+    before(async () => {
+      await useShortRounds();
+    });
+
     it("should place a bet on LEFT side", async () => {
-      // This test requires an active round
-      console.log("Skipping: requires active round from start_round");
+      const bettor = await newBettor();
+      const round = await startPythRound(OPEN_SECS);
+      const treasuryBefore = await provider.connection.getBalance(treasuryKeypair.publicKey);
+
+      await placeBet(round.roundPda, bettor, 0, STAKE);
+
+      const fee = Math.floor((STAKE * FEE_BPS) / 10_000);
+      const bet = await program.account.bet.fetch(betAddress(round.roundIdBytes, bettor.publicKey));
+      expect(bet.bettor.toBase58()).to.equal(bettor.publicKey.toBase58());
+      expect(bet.side).to.equal(0);
+      expect(bet.amount.toNumber()).to.equal(STAKE - fee);
+      expect(bet.originalAmount.toNumber()).to.equal(STAKE);
+      expect(bet.treasuryFee.toNumber()).to.equal(fee);
+
+      const state = await program.account.round.fetch(round.roundPda);
+      expect(state.leftPool.toNumber()).to.equal(STAKE - fee);
+      expect(state.leftWeightedPool.toString()).to.equal(bet.weightedAmount.toString());
+      expect(state.rightPool.toNumber()).to.equal(0);
+      expect(state.betCount).to.equal(1);
+      expect(
+        (await provider.connection.getBalance(treasuryKeypair.publicKey)) - treasuryBefore
+      ).to.equal(fee);
     });
 
     it("should reject bet below minimum", async () => {
-      console.log("Skipping: requires active round");
+      const bettor = await newBettor();
+      const round = await startPythRound(OPEN_SECS);
+      await expectError(placeBet(round.roundPda, bettor, 0, MIN_BET - 1), "BetTooSmall");
     });
 
     it("should reject bet above maximum", async () => {
-      console.log("Skipping: requires active round");
+      const bettor = await newBettor();
+      const round = await startPythRound(OPEN_SECS);
+      await expectError(placeBet(round.roundPda, bettor, 1, MAX_BET + 1), "BetTooLarge");
+    });
+
+    it("should top up an existing position on the same side", async () => {
+      const bettor = await newBettor();
+      const round = await startPythRound(OPEN_SECS);
+      const betPda = betAddress(round.roundIdBytes, bettor.publicKey);
+
+      await placeBet(round.roundPda, bettor, 1, STAKE);
+      const first = await program.account.bet.fetch(betPda);
+      await placeBet(round.roundPda, bettor, 1, STAKE / 2);
+
+      const bet = await program.account.bet.fetch(betPda);
+      const state = await program.account.round.fetch(round.roundPda);
+      expect(bet.originalAmount.toNumber()).to.equal(STAKE + STAKE / 2);
+      expect(bet.amount.gt(first.amount)).to.be.true;
+      expect(bet.betIndex).to.equal(first.betIndex);
+      expect(bet.betTime.toString()).to.equal(first.betTime.toString());
+      // One position, holding everything on its side
+      expect(state.betCount).to.equal(1);
+      expect(state.rightPool.toString()).to.equal(bet.amount.toString());
+      expect(state.rightWeightedPool.toString()).to.equal(bet.weightedAmount.toString());
+    });
+
+    it("should reject a top-up on the opposite side", async () => {
+      const bettor = await newBettor();
+      const round = await startPythRound(OPEN_SECS);
+      await placeBet(round.roundPda, bettor, 0, STAKE);
+
+      await expectError(placeBet(round.roundPda, bettor, 1, STAKE), "PositionSideMismatch");
+      const bet = await program.account.bet.fetch(betAddress(round.roundIdBytes, bettor.publicKey));
+      expect(bet.side).to.equal(0);
+      expect(bet.originalAmount.toNumber()).to.equal(STAKE);
+    });
+
+    it("should reject a bet whose implied multiplier is below the minimum", async () => {
+      const bettor = await newBettor();
+      const round = await startPythRound(OPEN_SECS);
+      await placeBet(round.roundPda, await newBettor(), 0, STAKE);
+
+      // About 1.95x with equal pools on both sides, far from 10x
+      await expectError(
+        placeBet(round.roundPda, bettor, 1, STAKE, new anchor.BN(100_000)),
+        "MultiplierBelowMinimum"
+      );
+      const { rightPool, betCount } = await program.account.round.fetch(round.roundPda);
+      expect(rightPool.toNumber()).to.equal(0);
+      expect(betCount).to.equal(1);
+
+      await placeBet(round.roundPda, bettor, 1, STAKE, new anchor.BN(15_000));
+      expect((await program.account.round.fetch(round.roundPda)).betCount).to.equal(2);
+    });
+
+    describe("weights", () => {
+      // Timing weight of a single bet (100 = 1x), exact since its amount is a
+      // multiple of 100
+      const betWeight = async (roundIdBytes: Buffer, bettor: PublicKey) => {
+        const bet = await program.account.bet.fetch(betAddress(roundIdBytes, bettor));
+        return { bet, weight: bet.weightedAmount.muln(100).div(bet.amount).toNumber() };
+      };
+
+      // Mirrors WeightCurve::weight_at for the default stepped curve
+      const defaultCurveWeight = (elapsed: number, duration: number) => {
+        const elapsedBps = Math.floor((Math.min(elapsed, duration) * 10_000) / duration);
+        const steps = DEFAULT_WEIGHT_CURVE.steps[0].filter((step) => step.startBps <= elapsedBps);
+        return steps[steps.length - 1].weight.toNumber();
+      };
+
+      after(async () => {
+        await setWeightCurve(DEFAULT_WEIGHT_CURVE);
+      });
+
+      it("should weigh a bet by how far into its round's window it lands", async () => {
+        const SHORT_WINDOW = 8;
+        const bettor = await newBettor();
+        const long = await startPythRound(new anchor.BN(SHORT_WINDOW * 10));
+        const short = await startPythRound(new anchor.BN(SHORT_WINDOW));
+
+        // Same time since open: early in the long window, late in the short one
+        const { startTime } = await program.account.round.fetch(short.roundPda);
+        await sleep((startTime.toNumber() + SHORT_WINDOW / 2 + 1) * 1000 - Date.now());
+        await placeBet(long.roundPda, bettor, 0, STAKE);
+        await placeBet(short.roundPda, bettor, 0, STAKE);
+
+        expect((await betWeight(long.roundIdBytes, bettor.publicKey)).weight).to.equal(150);
+        const { bet, weight } = await betWeight(short.roundIdBytes, bettor.publicKey);
+        expect(weight).to.equal(
+          defaultCurveWeight(bet.betTime.sub(startTime).toNumber(), SHORT_WINDOW)
+        );
+        expect(weight).to.be.below(130);
+      }).timeout(30_000);
+
+      it("should weigh a bet by the curve its round started with", async () => {
+        await setWeightCurve({ linear: { maxWeight: new anchor.BN(200) } });
+        const round = await startPythRound(OPEN_SECS);
+        await setWeightCurve(DEFAULT_WEIGHT_CURVE);
+
+        const state = await program.account.round.fetch(round.roundPda);
+        expect(state.weightCurve.linear.maxWeight.toNumber()).to.equal(200);
+
+        const bettor = await newBettor();
+        await placeBet(round.roundPda, bettor, 1, STAKE);
+        const { bet, weight } = await betWeight(round.roundIdBytes, bettor.publicKey);
+
+        // Linear decay from 2x at open to 1x at close
+        const duration = OPEN_SECS.toNumber();
+        const elapsed = bet.betTime.sub(state.startTime).toNumber();
+        expect(weight).to.equal(200 - Math.floor((100 * elapsed) / duration));
+      });
+
+      it("should keep an open round's weights when the config curve changes", async () => {
+        const round = await startPythRound(OPEN_SECS);
+        const early = await newBettor();
+        await placeBet(round.roundPda, early, 0, STAKE);
+        expect((await betWeight(round.roundIdBytes, early.publicKey)).weight).to.equal(150);
+
+        await setWeightCurve({ linear: { maxWeight: new anchor.BN(1000) } });
+        const late = await newBettor();
+        await placeBet(round.roundPda, late, 1, STAKE);
+        await setWeightCurve(DEFAULT_WEIGHT_CURVE);
+
+        expect((await betWeight(round.roundIdBytes, late.publicKey)).weight).to.equal(150);
+        const { weightCurve } = await program.account.round.fetch(round.roundPda);
+        const stepWeights = (steps: { startBps: number; weight: anchor.BN }[]) =>
+          steps.map((step) => [step.startBps, step.weight.toNumber()]);
+        expect(stepWeights(weightCurve.steps[0])).to.deep.equal(
+          stepWeights(DEFAULT_WEIGHT_CURVE.steps[0])
+        );
+      });
+    });
+
+    describe("exposure limits", () => {
+      const setExposureLimits = (perRound: number, daily: number) =>
+        program.methods
+          .setExposureLimits(new anchor.BN(perRound), new anchor.BN(daily))
+          .accounts({ admin: admin.publicKey })
+          .rpc();
+
+      after(async () => {
+        await setExposureLimits(0, 0);
+      });
+
+      it("should cap a wallet's position in one round", async () => {
+        await setExposureLimits(2 * STAKE, 0);
+        const bettor = await newBettor();
+        const round = await startPythRound(OPEN_SECS);
+
+        await placeBet(round.roundPda, bettor, 0, STAKE + STAKE / 2);
+        await expectError(placeBet(round.roundPda, bettor, 0, STAKE), "RoundExposureExceeded");
+        await placeBet(round.roundPda, bettor, 0, STAKE / 2);
+
+        const bet = await program.account.bet.fetch(betAddress(round.roundIdBytes, bettor.publicKey));
+        expect(bet.originalAmount.toNumber()).to.equal(2 * STAKE);
+      });
+
+      it("should cap a wallet's bets across rounds over 24 hours", async () => {
+        await setExposureLimits(0, 3 * STAKE);
+        const bettor = await newBettor();
+        const first = await startPythRound(OPEN_SECS);
+        const second = await startPythRound(OPEN_SECS);
+
+        await placeBet(first.roundPda, bettor, 0, 2 * STAKE);
+        await expectError(
+          placeBet(second.roundPda, bettor, 1, STAKE + STAKE / 2),
+          "DailyExposureExceeded"
+        );
+        await placeBet(second.roundPda, bettor, 1, STAKE);
+      });
+    });
+
+    const setDynamicMaxBet = (bps: number, floor: number) =>
+      program.methods
+        .setDynamicMaxBet(bps, new anchor.BN(floor))
+        .accounts({ admin: admin.publicKey })
+        .rpc();
+
+    it("should reject a dynamic max bet share above 100%", async () => {
+      await expectError(setDynamicMaxBet(10_001, STAKE), "InvalidDynamicMaxBetBps");
+    });
+
+    it("should limit a bet to a share of the round's pool above the floor", async () => {
+      // Half the pool, but always at least one STAKE
+      await setDynamicMaxBet(5_000, STAKE);
+      try {
+        const bettors = await Promise.all([...Array(4)].map(() => newBettor()));
+        const round = await startPythRound(OPEN_SECS);
+        const large = STAKE + (STAKE * 2) / 5;
+
+        await expectError(placeBet(round.roundPda, bettors[0], 0, large), "BetExceedsPoolLimit");
+        for (const bettor of bettors.slice(1)) {
+          await placeBet(round.roundPda, bettor, 1, STAKE);
+        }
+        // Three bets net of fees hold about 2.9 STAKE, so 1.4 STAKE now fits
+        await placeBet(round.roundPda, bettors[0], 0, large);
+      } finally {
+        await setDynamicMaxBet(0, 0);
+      }
     });
   });
 
+  describe("withdraw_bet", () => {
+    const EXIT_PENALTY_BPS = 500; // 5%
+    const STAKE = LAMPORTS_PER_SOL / 10;
+
+    const setExitPenalty = (bps: number, toTreasury: boolean) =>
+      program.methods
+        .setExitPenalty(bps, toTreasury)
+        .accounts({ admin: admin.publicKey })
+        .rpc();
+
+    const withdrawBet = (roundPda: PublicKey, bettor: anchor.web3.Keypair) =>
+      program.methods
+        .withdrawBet()
+        .accounts({
+          round: roundPda,
+          treasury: treasuryKeypair.publicKey,
+          bettor: bettor.publicKey,
+          bettorToken: null,
+          ...NO_TOKENS,
+        })
+        .signers([bettor])
+        .rpc();
+
+    const balance = (account: PublicKey) => provider.connection.getBalance(account);
+
+    before(async () => {
+      await useShortRounds();
+    });
+
+    after(async () => {
+      await setExitPenalty(EXIT_PENALTY_BPS, false);
+    });
+
+    it("should reject an exit penalty above 100%", async () => {
+      await expectError(setExitPenalty(10_001, false), "InvalidExitPenaltyBps");
+    });
+
+    it("should take a withdrawn bet out of its pools and keep the penalty for winners", async () => {
+      await setExitPenalty(EXIT_PENALTY_BPS, false);
+      const round = await startPythRound(new anchor.BN(60));
+      const bettor = await newBettor();
+      await placeBet(round.roundPda, bettor, 0, STAKE);
+      await placeBet(round.roundPda, await newBettor(), 1, STAKE);
+
+      const betPda = betAddress(round.roundIdBytes, bettor.publicKey);
+      const bet = await program.account.bet.fetch(betPda);
+      const before = await program.account.round.fetch(round.roundPda);
+      const balanceBefore = await balance(bettor.publicKey);
+
+      await withdrawBet(round.roundPda, bettor);
+
+      const penalty = bet.amount.muln(EXIT_PENALTY_BPS).divn(10_000);
+      const after = await program.account.round.fetch(round.roundPda);
+      expect(before.leftPool.sub(after.leftPool).toString()).to.equal(bet.amount.toString());
+      expect(before.leftWeightedPool.sub(after.leftWeightedPool).toString()).to.equal(
+        bet.weightedAmount.toString()
+      );
+      expect(after.rightPool.toString()).to.equal(before.rightPool.toString());
+      expect(after.bonusPool.sub(before.bonusPool).toString()).to.equal(penalty.toString());
+      expect((await balance(bettor.publicKey)) - balanceBefore).to.equal(
+        bet.amount.sub(penalty).toNumber()
+      );
+      expect((await program.account.bet.fetch(betPda)).withdrawn).to.be.true;
+
+      try {
+        await withdrawBet(round.roundPda, bettor);
+        expect.fail("expected BetWithdrawn");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("BetWithdrawn");
+      }
+    });
+
+    it("should send the exit penalty to the treasury when configured", async () => {
+      await setExitPenalty(EXIT_PENALTY_BPS, true);
+      const round = await startPythRound(new anchor.BN(60));
+      const bettor = await newBettor();
+      await placeBet(round.roundPda, bettor, 1, STAKE);
+
+      const bet = await program.account.bet.fetch(betAddress(round.roundIdBytes, bettor.publicKey));
+      const before = await program.account.round.fetch(round.roundPda);
+      const treasuryBefore = await balance(treasuryKeypair.publicKey);
+
+      await withdrawBet(round.roundPda, bettor);
+
+      const penalty = bet.amount.muln(EXIT_PENALTY_BPS).divn(10_000);
+      const after = await program.account.round.fetch(round.roundPda);
+      expect(after.rightPool.toNumber()).to.equal(0);
+      expect(after.rightWeightedPool.toNumber()).to.equal(0);
+      expect(after.bonusPool.toString()).to.equal(before.bonusPool.toString());
+      expect((await balance(treasuryKeypair.publicKey)) - treasuryBefore).to.equal(
+        penalty.toNumber()
+      );
+    });
+
+    it("should reject a withdrawal after betting closes", async () => {
+      const round = await startPythRound();
+      const bettor = await newBettor();
+      await placeBet(round.roundPda, bettor, 0, STAKE);
+
+      const { bettingEndTime } = await program.account.round.fetch(round.roundPda);
+      const waitMs = (bettingEndTime.toNumber() + 2) * 1000 - Date.now();
+      if (waitMs > 0) await sleep(waitMs);
+
+      try {
+        await withdrawBet(round.roundPda, bettor);
+        expect.fail("expected BettingPeriodEnded");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("BettingPeriodEnded");
+      }
+    }).timeout(20_000);
+  });
+
   describe("settle_round", () => {
     it("should settle round and determine winner", async () => {
-      console.log("Skipping: requires mock Pyth price feed with different price");
+      const [left, right] = [await newBettor(), await newBettor()];
+      const round = await startPythRound();
+      await placeBet(round.roundPda, left, 0, LAMPORTS_PER_SOL / 10);
+      await placeBet(round.roundPda, right, 1, LAMPORTS_PER_SOL / 10);
+
+      const endPrice = START_PRICE.price - 1_000_000n;
+      await settleRound(round, { ...START_PRICE, price: endPrice });
+
+      const state = await program.account.round.fetch(round.roundPda);
+      expect(state.status).to.deep.equal({ settling: {} });
+      expect(state.winningSide).to.deep.equal({ left: {} });
+      expect(state.endPrice.price.toString()).to.equal(endPrice.toString());
+    }).timeout(30_000);
+
+    it("should refund a round with bets on only one side", async () => {
+      const bettor = await newBettor();
+      const round = await startPythRound();
+      await placeBet(round.roundPda, bettor, 1, LAMPORTS_PER_SOL / 10);
+      await settleRound(round, { ...START_PRICE, price: START_PRICE.price + 1_000_000n });
+
+      let state = await program.account.round.fetch(round.roundPda);
+      expect(state.status).to.deep.equal({ refunding: {} });
+      expect(state.winningSide).to.be.null;
+
+      // The pool contribution comes back; fee refunds are off by default
+      const betPda = betAddress(round.roundIdBytes, bettor.publicKey);
+      const bet = await program.account.bet.fetch(betPda);
+      const before = await provider.connection.getBalance(bettor.publicKey);
+      await processPayout(round.roundPda, betPda, bettor.publicKey);
+      const after = await provider.connection.getBalance(bettor.publicKey);
+      expect(after - before).to.equal(bet.amount.toNumber());
+
+      state = await program.account.round.fetch(round.roundPda);
+      expect(state.payoutsProcessed).to.equal(1);
+      expect((await program.account.bet.fetch(betPda)).paidOut).to.be.true;
+    }).timeout(30_000);
+
+    describe("ties", () => {
+      const setTiePolicy = (tiePolicy: any) =>
+        program.methods.setTiePolicy(tiePolicy).accounts({ admin: admin.publicKey }).rpc();
+
+      // Round (started under `tiePolicy`) with a bet on each side, settled
+      // at its start price
+      const settleTie = async (tiePolicy: any) => {
+        await setTiePolicy(tiePolicy);
+        const round = await startPythRound();
+        await placeBet(round.roundPda, await newBettor(), 0, LAMPORTS_PER_SOL / 10);
+        await placeBet(round.roundPda, await newBettor(), 1, LAMPORTS_PER_SOL / 10);
+        await settleRound(round, START_PRICE);
+        return { round, state: await program.account.round.fetch(round.roundPda) };
+      };
+
+      after(async () => {
+        await setTiePolicy({ refund: {} });
+      });
+
+      it("should refund every bet under the Refund policy", async () => {
+        const { state } = await settleTie({ refund: {} });
+        expect(state.status).to.deep.equal({ refunding: {} });
+        expect(state.winningSide).to.be.null;
+      }).timeout(30_000);
+
+      it("should pay RIGHT under the RightWins policy", async () => {
+        const { state } = await settleTie({ rightWins: {} });
+        expect(state.status).to.deep.equal({ settling: {} });
+        expect(state.winningSide).to.deep.equal({ right: {} });
+      }).timeout(30_000);
+
+      it("should roll the pool into the next round under the Rollover policy", async () => {
+        const { round, state } = await settleTie({ rollover: {} });
+        expect(state.status).to.deep.equal({ settling: {} });
+        expect(state.winningSide).to.be.null;
+
+        const pool = state.leftPool.add(state.rightPool).add(state.bonusPool);
+        const config = await program.account.config.fetch(configPda);
+        expect(config.rolloverPool.toString()).to.equal(pool.toString());
+        const next = roundAddresses(config.roundCounter);
+        expect(await provider.connection.getBalance(next.vaultPda)).to.equal(pool.toNumber());
+
+        // The round vault keeps only its rent reserve
+        const rentReserve = await provider.connection.getMinimumBalanceForRentExemption(0);
+        expect(await provider.connection.getBalance(round.vaultPda)).to.equal(rentReserve);
+
+        const nextRound = await startPythRound();
+        expect(nextRound.roundPda.toString()).to.equal(next.roundPda.toString());
+        const { bonusPool } = await program.account.round.fetch(nextRound.roundPda);
+        expect(bonusPool.toString()).to.equal(pool.toString());
+        expect(
+          (await program.account.config.fetch(configPda)).rolloverPool.toNumber()
+        ).to.equal(0);
+      }).timeout(30_000);
+    });
+
+    it("should reject an end price for a different Pyth feed", async () => {
+      await useShortRounds();
+      const round = await startPythRound();
+      const { priceFeedId } = await program.account.round.fetch(round.roundPda);
+      expect(priceFeedId).to.deep.equal(Array(32).fill(0));
+
+      try {
+        // Same account, rewritten with another feed's verified update
+        await settleRound(round, { ...START_PRICE, feedId: Buffer.alloc(32, 7) });
+        expect.fail("expected PriceFeedMismatch");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("PriceFeedMismatch");
+      }
+    }).timeout(30_000);
+  });
+
+  describe("void_round", () => {
+    const GRACE_SECS = 3;
+
+    before(async () => {
+      await useShortRounds();
+      await setVoidGracePeriod(GRACE_SECS);
+    });
+
+    after(async () => {
+      await setVoidGracePeriod(DEFAULT_VOID_GRACE_SECS);
+    });
+
+    it("should void a round left unsettled once the grace period ends", async () => {
+      const round = await startPythRound();
+      await placeBet(round.roundPda, await newBettor(), 0, LAMPORTS_PER_SOL / 10);
+      const voidRound = () =>
+        program.methods
+          .voidRound()
+          .accounts({ round: round.roundPda, proposer: null, disputer: null })
+          .rpc();
+
+      try {
+        await voidRound();
+        expect.fail("expected VoidGracePeriodActive");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("VoidGracePeriodActive");
+      }
+
+      const { endTime } = await program.account.round.fetch(round.roundPda);
+      await sleep((endTime.toNumber() + GRACE_SECS + 2) * 1000 - Date.now());
+      await voidRound();
+
+      const state = await program.account.round.fetch(round.roundPda);
+      expect(state.status).to.deep.equal({ refunding: {} });
+      expect(state.winningSide).to.be.null;
+    }).timeout(30_000);
+  });
+
+  describe("twap settlement", () => {
+    const TWAP_SAMPLES = 3;
+    // Room for TWAP_SAMPLES samples a couple of seconds apart
+    const TWAP_WAITING_SECS = new anchor.BN(10);
+
+    const setTwapConfig = (minSamples: number, minSpacingSecs: number) =>
+      program.methods
+        .setTwapConfig(minSamples, new anchor.BN(minSpacingSecs))
+        .accounts({ admin: admin.publicKey })
+        .rpc();
+
+    const priceSamplesAddress = (roundIdBytes: Buffer) =>
+      PublicKey.findProgramAddressSync(
+        [Buffer.from("samples"), roundIdBytes],
+        program.programId
+      )[0];
+
+    // Publish `price` on the round's feed now and record it as a sample
+    const recordSample = async (
+      round: { roundPda: PublicKey; priceFeed: PublicKey },
+      price: bigint
+    ) => {
+      await setMockPrice(provider, round.priceFeed, { ...START_PRICE, price });
+      await program.methods
+        .recordPriceSample()
+        .accounts({
+          round: round.roundPda,
+          priceFeed: round.priceFeed,
+          cranker: admin.publicKey,
+        })
+        .rpc();
+    };
+
+    const settleTwapRound = (round: {
+      roundPda: PublicKey;
+      priceFeed: PublicKey;
+      roundIdBytes: Buffer;
+    }) =>
+      program.methods
+        .settleRound(null)
+        .accounts({
+          round: round.roundPda,
+          priceFeed: round.priceFeed,
+          switchboardFeed: null,
+          priceSamples: priceSamplesAddress(round.roundIdBytes),
+          settler: admin.publicKey,
+        })
+        .rpc();
+
+    const waitUntil = async (unixSecs: number) => {
+      const waitMs = unixSecs * 1000 - Date.now();
+      if (waitMs > 0) await sleep(waitMs);
+    };
+
+    before(async () => {
+      await useShortRounds();
+      await setTwapConfig(2, 1);
+    });
+
+    after(async () => {
+      await setTwapConfig(4, 5 * 60); // Config::DEFAULT_TWAP_MIN_*
     });
+
+    it("should settle at the TWAP of samples recorded in the waiting period", async () => {
+      const round = await startPythRound(SHORT_BETTING_SECS, TWAP_WAITING_SECS, TWAP_SAMPLES);
+      await placeBet(round.roundPda, await newBettor(), 0, LAMPORTS_PER_SOL / 10);
+      await placeBet(round.roundPda, await newBettor(), 1, LAMPORTS_PER_SOL / 10);
+
+      // Betting is still open
+      await expectError(recordSample(round, START_PRICE.price), "SamplingNotOpen");
+
+      const { bettingEndTime, endTime } = await program.account.round.fetch(round.roundPda);
+      await waitUntil(bettingEndTime.toNumber() + 1);
+      for (const offset of [1_000_000n, 3_000_000n, 2_000_000n]) {
+        await recordSample(round, START_PRICE.price + offset);
+        await sleep(1_500);
+      }
+
+      const samplesPda = priceSamplesAddress(round.roundIdBytes);
+      const { samples } = await program.account.priceSamples.fetch(samplesPda);
+      expect(samples).to.have.length(TWAP_SAMPLES);
+
+      // Each price holds until the next sample (see PriceSamples::twap)
+      const weighted = samples
+        .slice(0, -1)
+        .map((sample, i) =>
+          sample.price.mul(samples[i + 1].publishTime.sub(sample.publishTime))
+        )
+        .reduce((total, value) => total.add(value), new anchor.BN(0));
+      const twap = weighted.div(samples[TWAP_SAMPLES - 1].publishTime.sub(samples[0].publishTime));
+
+      // Sampling closes with the round
+      await waitUntil(endTime.toNumber() + 2);
+      await expectError(recordSample(round, START_PRICE.price), "SamplingClosed");
+
+      const closeSamples = () =>
+        program.methods
+          .closePriceSamples()
+          .accounts({ round: round.roundPda, payer: admin.publicKey })
+          .rpc();
+      await expectError(closeSamples(), "RoundNotFinished");
+
+      await settleTwapRound(round);
+      const state = await program.account.round.fetch(round.roundPda);
+      expect(state.status).to.deep.equal({ settling: {} });
+      expect(state.winningSide).to.deep.equal({ right: {} });
+      expect(state.endPrice.price.toString()).to.equal(twap.toString());
+
+      await closeSamples();
+      expect(await provider.connection.getAccountInfo(samplesPda)).to.be.null;
+    }).timeout(40_000);
+
+    it("should not settle with fewer samples than the round needs", async () => {
+      const round = await startPythRound(SHORT_BETTING_SECS, TWAP_WAITING_SECS, TWAP_SAMPLES);
+      await placeBet(round.roundPda, await newBettor(), 0, LAMPORTS_PER_SOL / 10);
+      await placeBet(round.roundPda, await newBettor(), 1, LAMPORTS_PER_SOL / 10);
+
+      const { bettingEndTime, endTime } = await program.account.round.fetch(round.roundPda);
+      await waitUntil(bettingEndTime.toNumber() + 1);
+      await recordSample(round, START_PRICE.price + 1_000_000n);
+      await sleep(1_500);
+      await recordSample(round, START_PRICE.price + 2_000_000n);
+
+      await waitUntil(endTime.toNumber() + 2);
+      await expectError(settleTwapRound(round), "NotEnoughSamples");
+      const { status } = await program.account.round.fetch(round.roundPda);
+      expect(status).to.deep.equal({ open: {} });
+    }).timeout(40_000);
   });
 
   describe("process_payout", () => {
     it("should pay out winners proportionally", async () => {
-      console.log("Skipping: requires settled round");
+      const [loser, small, large] = [await newBettor(), await newBettor(), await newBettor()];
+      const round = await startPythRound();
+      await placeBet(round.roundPda, loser, 0, LAMPORTS_PER_SOL / 5);
+      await placeBet(round.roundPda, small, 1, LAMPORTS_PER_SOL / 10);
+      await placeBet(round.roundPda, large, 1, (LAMPORTS_PER_SOL * 3) / 10);
+      await settleRound(round, { ...START_PRICE, price: START_PRICE.price + 1_000_000n });
+
+      for (const winner of [small, large]) {
+        const betPda = betAddress(round.roundIdBytes, winner.publicKey);
+        const payout = await rightWinnerPayout(round.roundPda, betPda);
+        const before = await provider.connection.getBalance(winner.publicKey);
+        await processPayout(round.roundPda, betPda, winner.publicKey);
+        const after = await provider.connection.getBalance(winner.publicKey);
+        expect(after - before).to.equal(payout.toNumber());
+      }
+
+      const loserBet = betAddress(round.roundIdBytes, loser.publicKey);
+      const loserBefore = await provider.connection.getBalance(loser.publicKey);
+      await processPayout(round.roundPda, loserBet, loser.publicKey);
+      expect(await provider.connection.getBalance(loser.publicKey)).to.equal(loserBefore);
+
+      const state = await program.account.round.fetch(round.roundPda);
+      expect(state.payoutsProcessed).to.equal(3);
+      expect(state.status).to.deep.equal({ settled: {} });
+    }).timeout(30_000);
+
+    it("should close a paid-out round once every bet is closed and the vault swept", async () => {
+      const bettors = [await newBettor(), await newBettor()];
+      const round = await startPythRound();
+      await placeBet(round.roundPda, bettors[0], 0, LAMPORTS_PER_SOL / 10);
+      await placeBet(round.roundPda, bettors[1], 1, LAMPORTS_PER_SOL / 10);
+      await settleRound(round, { ...START_PRICE, price: START_PRICE.price + 1_000_000n });
+
+      const betPdas = bettors.map((bettor) => betAddress(round.roundIdBytes, bettor.publicKey));
+      for (const [i, bettor] of bettors.entries()) {
+        await processPayout(round.roundPda, betPdas[i], bettor.publicKey);
+      }
+
+      const closeRound = () =>
+        program.methods
+          .closeRound()
+          .accounts({ admin: admin.publicKey, round: round.roundPda })
+          .rpc();
+      const expectBetsNotClosed = async () => {
+        try {
+          await closeRound();
+          expect.fail("expected BetsNotClosed");
+        } catch (err: any) {
+          expect(err.error.errorCode.code).to.equal("BetsNotClosed");
+        }
+      };
+
+      await expectBetsNotClosed();
+      for (const [i, bettor] of bettors.entries()) {
+        const before = await provider.connection.getBalance(bettor.publicKey);
+        const betRent = (await provider.connection.getAccountInfo(betPdas[i])).lamports;
+        await program.methods
+          .closeBet()
+          .accounts({ round: round.roundPda, bet: betPdas[i], bettor: bettor.publicKey })
+          .rpc();
+        const after = await provider.connection.getBalance(bettor.publicKey);
+        expect(after - before).to.equal(betRent);
+
+        // Still one bet open after the first
+        if (i === 0) await expectBetsNotClosed();
+      }
+
+      const { betsClosed, betCount } = await program.account.round.fetch(round.roundPda);
+      expect(betsClosed).to.equal(betCount);
+
+      await program.methods
+        .sweepVault()
+        .accounts({
+          round: round.roundPda,
+          treasury: treasuryKeypair.publicKey,
+          stakeMint: null,
+          vaultToken: null,
+          treasuryToken: null,
+          tokenProgram: null,
+        })
+        .rpc();
+      expect(await provider.connection.getBalance(round.vaultPda)).to.equal(0);
+
+      await closeRound();
+      expect(await provider.connection.getAccountInfo(round.roundPda)).to.be.null;
+    }).timeout(30_000);
+  });
+
+  describe("claim", () => {
+    it("should reject claims before the round settles", async () => {
+      const bettor = await newBettor();
+      const { roundPda } = await startPythRound(BETTING_SECS, WAITING_SECS);
+      await placeBet(roundPda, bettor, 1, MIN_BET);
+
+      await expectError(
+        program.methods
+          .claim()
+          .accounts({
+            round: roundPda,
+            bettor: bettor.publicKey,
+            treasury: null,
+            bettorToken: null,
+            ...NO_TOKENS,
+          })
+          .signers([bettor])
+          .rpc(),
+        "RoundNotSettling"
+      );
+    });
+
+    it("should pay a winner who claims their own payout", async () => {
+      const [winner, loser] = [await newBettor(), await newBettor()];
+      const round = await startPythRound();
+      await placeBet(round.roundPda, winner, 1, LAMPORTS_PER_SOL / 10);
+      await placeBet(round.roundPda, loser, 0, LAMPORTS_PER_SOL / 10);
+      await settleRound(round, { ...START_PRICE, price: START_PRICE.price + 1_000_000n });
+
+      const betPda = betAddress(round.roundIdBytes, winner.publicKey);
+      const payout = await rightWinnerPayout(round.roundPda, betPda);
+      const before = await provider.connection.getBalance(winner.publicKey);
+      await program.methods
+        .claim()
+        .accounts({
+          round: round.roundPda,
+          bettor: winner.publicKey,
+          treasury: null,
+          bettorToken: null,
+          ...NO_TOKENS,
+        })
+        .signers([winner])
+        .rpc();
+      const after = await provider.connection.getBalance(winner.publicKey);
+      expect(after - before).to.equal(payout.toNumber());
+      expect((await program.account.bet.fetch(betPda)).paidOut).to.be.true;
+      expect((await program.account.round.fetch(round.roundPda)).payoutsProcessed).to.equal(1);
+
+      // The keeper loop can't pay it twice
+      try {
+        await processPayout(round.roundPda, betPda, winner.publicKey);
+        expect.fail("expected PayoutAlreadyProcessed");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("PayoutAlreadyProcessed");
+      }
+    }).timeout(30_000);
+  });
+
+  describe("process_payouts_batch", () => {
+    it("should reject a batch before the round settles", async () => {
+      const bettor = await newBettor();
+      const { roundPda, roundIdBytes } = await startPythRound(BETTING_SECS, WAITING_SECS);
+      await placeBet(roundPda, bettor, 1, MIN_BET);
+
+      await expectError(
+        program.methods
+          .processPayoutsBatch()
+          .accounts({ round: roundPda, treasury: null, ...NO_TOKENS })
+          .remainingAccounts([
+            {
+              pubkey: betAddress(roundIdBytes, bettor.publicKey),
+              isSigner: false,
+              isWritable: true,
+            },
+            { pubkey: bettor.publicKey, isSigner: false, isWritable: true },
+          ])
+          .rpc(),
+        "RoundNotSettling"
+      );
+    });
+
+    it("should pay a batch of bets, skipping those already paid", async () => {
+      const bettors = [await newBettor(), await newBettor(), await newBettor()];
+      const [claimed, winner, loser] = bettors;
+      const round = await startPythRound();
+      await placeBet(round.roundPda, claimed, 1, LAMPORTS_PER_SOL / 10);
+      await placeBet(round.roundPda, winner, 1, LAMPORTS_PER_SOL / 10);
+      await placeBet(round.roundPda, loser, 0, LAMPORTS_PER_SOL / 5);
+      await settleRound(round, { ...START_PRICE, price: START_PRICE.price + 1_000_000n });
+
+      const claimedBet = betAddress(round.roundIdBytes, claimed.publicKey);
+      await processPayout(round.roundPda, claimedBet, claimed.publicKey);
+      const claimedBefore = await provider.connection.getBalance(claimed.publicKey);
+
+      const winnerBet = betAddress(round.roundIdBytes, winner.publicKey);
+      const payout = await rightWinnerPayout(round.roundPda, winnerBet);
+      const winnerBefore = await provider.connection.getBalance(winner.publicKey);
+
+      await program.methods
+        .processPayoutsBatch()
+        .accounts({
+          round: round.roundPda,
+          treasury: null,
+          stakeMint: null,
+          vaultToken: null,
+          treasuryToken: null,
+          tokenProgram: null,
+        })
+        .remainingAccounts(
+          bettors.flatMap((bettor) => [
+            {
+              pubkey: betAddress(round.roundIdBytes, bettor.publicKey),
+              isSigner: false,
+              isWritable: true,
+            },
+            { pubkey: bettor.publicKey, isSigner: false, isWritable: true },
+          ])
+        )
+        .rpc();
+
+      expect(await provider.connection.getBalance(claimed.publicKey)).to.equal(claimedBefore);
+      expect((await provider.connection.getBalance(winner.publicKey)) - winnerBefore).to.equal(
+        payout.toNumber()
+      );
+      for (const bettor of bettors) {
+        const bet = await program.account.bet.fetch(betAddress(round.roundIdBytes, bettor.publicKey));
+        expect(bet.paidOut).to.be.true;
+      }
+
+      const state = await program.account.round.fetch(round.roundPda);
+      expect(state.payoutsProcessed).to.equal(3);
+      expect(state.status).to.deep.equal({ settled: {} });
+    }).timeout(30_000);
+  });
+
+  describe("merkle settlement", () => {
+    it("should reject a payout root before the round settles", async () => {
+      const { roundPda } = await startPythRound(BETTING_SECS, WAITING_SECS);
+      await expectError(
+        program.methods
+          .postPayoutRoot(Array(32).fill(0), new anchor.BN(0), 1, new anchor.BN(60))
+          .accounts({
+            round: roundPda,
+            admin: admin.publicKey,
+            stakeMint: null,
+            vaultToken: null,
+            tokenProgram: null,
+          })
+          .rpc(),
+        "RoundNotSettling"
+      );
     });
+
+    const payoutRootAddress = (roundIdBytes: Buffer) =>
+      PublicKey.findProgramAddressSync(
+        [Buffer.from("payout_root"), roundIdBytes],
+        program.programId
+      )[0];
+
+    // Settle a round RIGHT wins, between one bettor on each side, and post a
+    // root of (winner, payout) and (loser, 0) claimable for `claimWindowSecs`
+    const postWinnerRoot = async (claimWindowSecs: number) => {
+      const round = await startPythRound();
+      const winner = await newBettor();
+      const loser = await newBettor();
+      await placeBet(round.roundPda, winner, 1, LAMPORTS_PER_SOL / 10);
+      await placeBet(round.roundPda, loser, 0, LAMPORTS_PER_SOL / 10);
+      await settleRound(round, { ...START_PRICE, price: START_PRICE.price + 1_000_000n });
+
+      const { leftPool, rightWeightedPool, bonusPool } = await program.account.round.fetch(
+        round.roundPda
+      );
+      const bet = await program.account.bet.fetch(betAddress(round.roundIdBytes, winner.publicKey));
+      const payout = bet.amount.add(
+        bet.weightedAmount.mul(leftPool.add(bonusPool)).div(rightWeightedPool)
+      );
+
+      const tree = merkleTree([
+        payoutLeaf(0, winner.publicKey, payout),
+        payoutLeaf(1, loser.publicKey, new anchor.BN(0)),
+      ]);
+      await program.methods
+        .postPayoutRoot([...tree.root], payout, 2, new anchor.BN(claimWindowSecs))
+        .accounts({
+          round: round.roundPda,
+          admin: admin.publicKey,
+          stakeMint: null,
+          vaultToken: null,
+          tokenProgram: null,
+        })
+        .rpc();
+
+      const claim = (amount: anchor.BN, proof: Buffer[]) =>
+        program.methods
+          .claimWithProof(0, amount, proof.map((node) => [...node]))
+          .accounts({
+            round: round.roundPda,
+            bettor: winner.publicKey,
+            bettorToken: null,
+            stakeMint: null,
+            vaultToken: null,
+            tokenProgram: null,
+          })
+          .signers([winner])
+          .rpc();
+
+      return { round, winner, payout, proof: tree.proof(0), claim };
+    };
+
+    it("should pay a claim proven against the posted root", async () => {
+      const { round, winner, payout, proof, claim } = await postWinnerRoot(60);
+      expect(proof).to.have.length(1);
+
+      try {
+        await claim(payout.addn(1), proof);
+        expect.fail("expected InvalidMerkleProof");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("InvalidMerkleProof");
+      }
+
+      const before = await provider.connection.getBalance(winner.publicKey);
+      await claim(payout, proof);
+      const after = await provider.connection.getBalance(winner.publicKey);
+      expect(after - before).to.equal(payout.toNumber());
+
+      const payoutRoot = await program.account.payoutRoot.fetch(
+        payoutRootAddress(round.roundIdBytes)
+      );
+      expect(payoutRoot.claimedCount).to.equal(1);
+      expect(payoutRoot.claimedAmount.toString()).to.equal(payout.toString());
+
+      try {
+        await claim(payout, proof);
+        expect.fail("expected PayoutAlreadyProcessed");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("PayoutAlreadyProcessed");
+      }
+    }).timeout(30_000);
+
+    it("should pay every claim of a root up to the vault's usable balance", async () => {
+      const round = await startPythRound();
+      const winners = [await newBettor(), await newBettor()];
+      for (const winner of winners) {
+        await placeBet(round.roundPda, winner, 1, LAMPORTS_PER_SOL / 10);
+      }
+      await placeBet(round.roundPda, await newBettor(), 0, LAMPORTS_PER_SOL / 10);
+      await settleRound(round, { ...START_PRICE, price: START_PRICE.price + 1_000_000n });
+
+      // Everything but the vault's rent reserve, split between the winners
+      const rentReserve = await provider.connection.getMinimumBalanceForRentExemption(0);
+      const usable = (await provider.connection.getBalance(round.vaultPda)) - rentReserve;
+      const payouts = [Math.floor(usable / 2), usable - Math.floor(usable / 2)].map(
+        (payout) => new anchor.BN(payout)
+      );
+      const tree = merkleTree(
+        winners.map((winner, i) => payoutLeaf(i, winner.publicKey, payouts[i]))
+      );
+
+      const postRoot = (total: number) =>
+        program.methods
+          .postPayoutRoot([...tree.root], new anchor.BN(total), 2, new anchor.BN(60))
+          .accounts({
+            round: round.roundPda,
+            admin: admin.publicKey,
+            stakeMint: null,
+            vaultToken: null,
+            tokenProgram: null,
+          })
+          .rpc();
+
+      try {
+        await postRoot(usable + 1);
+        expect.fail("expected PayoutRootExceedsVault");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("PayoutRootExceedsVault");
+      }
+      await postRoot(usable);
+
+      for (const [i, winner] of winners.entries()) {
+        const before = await provider.connection.getBalance(winner.publicKey);
+        await program.methods
+          .claimWithProof(i, payouts[i], tree.proof(i).map((node) => [...node]))
+          .accounts({
+            round: round.roundPda,
+            bettor: winner.publicKey,
+            bettorToken: null,
+            stakeMint: null,
+            vaultToken: null,
+            tokenProgram: null,
+          })
+          .signers([winner])
+          .rpc();
+        const after = await provider.connection.getBalance(winner.publicKey);
+        expect(after - before).to.equal(payouts[i].toNumber());
+      }
+
+      expect(await provider.connection.getBalance(round.vaultPda)).to.equal(rentReserve);
+      const payoutRoot = await program.account.payoutRoot.fetch(
+        payoutRootAddress(round.roundIdBytes)
+      );
+      expect(payoutRoot.claimedCount).to.equal(2);
+      expect(payoutRoot.claimedAmount.toNumber()).to.equal(usable);
+      const { status } = await program.account.round.fetch(round.roundPda);
+      expect(status).to.deep.equal({ settled: {} });
+    }).timeout(30_000);
+
+    it("should finalize a round left unclaimed after the claim deadline", async () => {
+      const { round, payout, proof, claim } = await postWinnerRoot(5);
+      const closePayoutRoot = () =>
+        program.methods
+          .closePayoutRoot()
+          .accounts({ round: round.roundPda, admin: admin.publicKey })
+          .rpc();
+
+      try {
+        await closePayoutRoot();
+        expect.fail("expected ClaimDeadlineNotPassed");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("ClaimDeadlineNotPassed");
+      }
+
+      await sleep(7_000);
+      try {
+        await claim(payout, proof);
+        expect.fail("expected ClaimDeadlinePassed");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("ClaimDeadlinePassed");
+      }
+
+      await closePayoutRoot();
+      const { status } = await program.account.round.fetch(round.roundPda);
+      expect(status).to.deep.equal({ settled: {} });
+      expect(
+        await provider.connection.getAccountInfo(payoutRootAddress(round.roundIdBytes))
+      ).to.be.null;
+
+      // The unclaimed payout goes to the treasury with the vault
+      const before = await provider.connection.getBalance(treasuryKeypair.publicKey);
+      await program.methods
+        .sweepVault()
+        .accounts({
+          round: round.roundPda,
+          treasury: treasuryKeypair.publicKey,
+          stakeMint: null,
+          vaultToken: null,
+          treasuryToken: null,
+          tokenProgram: null,
+        })
+        .rpc();
+      const after = await provider.connection.getBalance(treasuryKeypair.publicKey);
+      expect(after - before).to.be.at.least(payout.toNumber());
+    }).timeout(40_000);
+  });
+
+  describe("disputes", () => {
+    const BOND = LAMPORTS_PER_SOL / 10;
+    const CHALLENGE_SECS = 4;
+    const HIGHER_PRICE: MockPrice = { ...START_PRICE, price: START_PRICE.price + 1_000_000n };
+
+    const setDisputeConfig = (windowSecs: number, bond: number) =>
+      program.methods
+        .setDisputeConfig(new anchor.BN(windowSecs), new anchor.BN(bond))
+        .accounts({ admin: admin.publicKey })
+        .rpc();
+
+    // Round with a bet on each side, proposed at a higher end price (RIGHT wins)
+    const proposeRound = async () => {
+      const round = await startPythRound();
+      await placeBet(round.roundPda, await newBettor(), 0, LAMPORTS_PER_SOL / 10);
+      await placeBet(round.roundPda, await newBettor(), 1, LAMPORTS_PER_SOL / 10);
+
+      const proposer = await newBettor();
+      await settleRound(round, HIGHER_PRICE, proposer);
+      return { round, proposer };
+    };
+
+    const balance = (account: PublicKey) => provider.connection.getBalance(account);
+
+    before(async () => {
+      await setDisputeConfig(CHALLENGE_SECS, BOND);
+    });
+
+    after(async () => {
+      await setDisputeConfig(0, 0);
+    });
+
+    it("should reject a bond below the rent-exempt minimum", async () => {
+      try {
+        await setDisputeConfig(CHALLENGE_SECS, 1_000);
+        expect.fail("expected InvalidDisputeConfig");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("InvalidDisputeConfig");
+      }
+    });
+
+    it("should return the bond when an undisputed proposal is finalized", async () => {
+      const { round, proposer } = await proposeRound();
+      const afterBond = await balance(proposer.publicKey);
+
+      let state = await program.account.round.fetch(round.roundPda);
+      expect(state.status).to.deep.equal({ proposed: {} });
+      expect(state.settlementBond.toNumber()).to.equal(BOND);
+
+      const finalize = () =>
+        program.methods
+          .finalizeSettlement()
+          .accounts({ round: round.roundPda, proposer: proposer.publicKey })
+          .rpc();
+
+      try {
+        await finalize();
+        expect.fail("expected ChallengeWindowOpen");
+      } catch (err: any) {
+        expect(err.error.errorCode.code).to.equal("ChallengeWindowOpen");
+      }
+
+      await sleep((CHALLENGE_SECS + 2) * 1000);
+      await finalize();
+
+      state = await program.account.round.fetch(round.roundPda);
+      expect(state.status).to.deep.equal({ settling: {} });
+      expect(state.winningSide).to.deep.equal({ right: {} });
+      expect((await balance(proposer.publicKey)) - afterBond).to.equal(BOND);
+    }).timeout(40_000);
+
+    it("should pay both bonds to a disputer proven right", async () => {
+      const { round, proposer } = await proposeRound();
+      const disputer = await newBettor();
+      const proposerBefore = await balance(proposer.publicKey);
+      const disputerBefore = await balance(disputer.publicKey);
+
+      await program.methods
+        .disputeSettlement()
+        .accounts({ round: round.roundPda, disputer: disputer.publicKey })
+        .signers([disputer])
+        .rpc();
+      expect(disputerBefore - (await balance(disputer.publicKey))).to.equal(BOND);
+
+      // The price actually fell: LEFT wins, so the proposal was wrong
+      const { endTime } = await program.account.round.fetch(round.roundPda);
+      await program.methods
+        .resolveDispute({
+          price: new anchor.BN((START_PRICE.price - 1_000_000n).toString()),
+          conf: new anchor.BN(START_PRICE.conf.toString()),
+          expo: START_PRICE.expo,
+          publishTime: endTime,
+        })
+        .accounts({
+          round: round.roundPda,
+          proposer: proposer.publicKey,
+          disputer: disputer.publicKey,
+          resolver: admin.publicKey,
+        })
+        .rpc();
+
+      const state = await program.account.round.fetch(round.roundPda);
+      expect(state.status).to.deep.equal({ settling: {} });
+      expect(state.winningSide).to.deep.equal({ left: {} });
+      expect((await balance(disputer.publicKey)) - disputerBefore).to.equal(BOND);
+      expect(await balance(proposer.publicKey)).to.equal(proposerBefore);
+    }).timeout(40_000);
+
+    it("should return both bonds when an unresolved dispute is voided", async () => {
+      const GRACE_SECS = 3;
+      const { round, proposer } = await proposeRound();
+      const disputer = await newBettor();
+      await program.methods
+        .disputeSettlement()
+        .accounts({ round: round.roundPda, disputer: disputer.publicKey })
+        .signers([disputer])
+        .rpc();
+      const proposerBefore = await balance(proposer.publicKey);
+      const disputerBefore = await balance(disputer.publicKey);
+
+      const voidRound = () =>
+        program.methods
+          .voidRound()
+          .accounts({
+            round: round.roundPda,
+            proposer: proposer.publicKey,
+            disputer: disputer.publicKey,
+          })
+          .rpc();
+
+      await setVoidGracePeriod(GRACE_SECS);
+      try {
+        try {
+          await voidRound();
+          expect.fail("expected VoidGracePeriodActive");
+        } catch (err: any) {
+          expect(err.error.errorCode.code).to.equal("VoidGracePeriodActive");
+        }
+
+        const { challengeEndTime } = await program.account.round.fetch(round.roundPda);
+        await sleep((challengeEndTime.toNumber() + GRACE_SECS + 2) * 1000 - Date.now());
+        await voidRound();
+      } finally {
+        await setVoidGracePeriod(DEFAULT_VOID_GRACE_SECS);
+      }
+
+      const state = await program.account.round.fetch(round.roundPda);
+      expect(state.status).to.deep.equal({ refunding: {} });
+      expect((await balance(proposer.publicKey)) - proposerBefore).to.equal(BOND);
+      expect((await balance(disputer.publicKey)) - disputerBefore).to.equal(BOND);
+    }).timeout(40_000);
   });
 });
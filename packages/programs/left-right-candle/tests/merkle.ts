@@ -0,0 +1,46 @@
+import * as anchor from "@coral-xyz/anchor";
+import { PublicKey } from "@solana/web3.js";
+import { createHash } from "crypto";
+
+const LEAF_PREFIX = Buffer.from([0]);
+const NODE_PREFIX = Buffer.from([1]);
+
+const sha256 = (...parts: Buffer[]) =>
+  createHash("sha256").update(Buffer.concat(parts)).digest();
+
+// Mirrors merkle::payout_leaf in src/merkle.rs
+export function payoutLeaf(index: number, bettor: PublicKey, amount: anchor.BN): Buffer {
+  const indexBytes = Buffer.alloc(4);
+  indexBytes.writeUInt32LE(index);
+  return sha256(LEAF_PREFIX, indexBytes, bettor.toBuffer(), amount.toArrayLike(Buffer, "le", 8));
+}
+
+// Pairs are hashed in sorted order, like merkle::verify
+const hashPair = (a: Buffer, b: Buffer) =>
+  Buffer.compare(a, b) <= 0 ? sha256(NODE_PREFIX, a, b) : sha256(NODE_PREFIX, b, a);
+
+// Tree over `leaves`; an unpaired node moves up a level unchanged
+export function merkleTree(leaves: Buffer[]) {
+  const levels: Buffer[][] = [leaves];
+  while (levels[levels.length - 1].length > 1) {
+    const level = levels[levels.length - 1];
+    const next: Buffer[] = [];
+    for (let i = 0; i < level.length; i += 2) {
+      next.push(i + 1 < level.length ? hashPair(level[i], level[i + 1]) : level[i]);
+    }
+    levels.push(next);
+  }
+
+  // Sibling hashes from leaf `index` up to the root
+  const proof = (index: number): Buffer[] => {
+    const siblings: Buffer[] = [];
+    for (const level of levels.slice(0, -1)) {
+      const sibling = index ^ 1;
+      if (sibling < level.length) siblings.push(level[sibling]);
+      index >>= 1;
+    }
+    return siblings;
+  };
+
+  return { root: levels[levels.length - 1][0], proof };
+}
@@ -0,0 +1,153 @@
+import * as anchor from "@coral-xyz/anchor";
+import {
+  Keypair,
+  PublicKey,
+  SystemProgram,
+  Transaction,
+  TransactionInstruction,
+} from "@solana/web3.js";
+
+// The mock-oracle program is loaded at the real oracle addresses on localnet
+export const PYTH_RECEIVER_PROGRAM_ID = new PublicKey(
+  "rec5EKMGg6MxZYaMdyBfgwp4d5rB9T1VQH5pJv5LtFJ"
+);
+export const SWITCHBOARD_PROGRAM_ID = new PublicKey(
+  "SW1TCH7qEPTdLsDHRgPuMQjbQxKdH2aBStViMFnt64f"
+);
+
+// sha256("account:PriceUpdateV2")[0:8]
+const PRICE_UPDATE_V2_DISCRIMINATOR = Buffer.from([34, 241, 35, 99, 157, 126, 244, 205]);
+const PRICE_UPDATE_V2_LEN = 134;
+
+// sha256("account:AggregatorAccountData")[0:8]
+const AGGREGATOR_DISCRIMINATOR = Buffer.from([217, 230, 65, 101, 201, 162, 27, 125]);
+const AGGREGATOR_LEN = 3851;
+const LATEST_ROUND_OFFSET = 341;
+
+export interface MockPrice {
+  price: bigint;
+  conf: bigint;
+  expo: number;
+  publishTime?: number;
+  // Pyth feed id (32 bytes, zero by default)
+  feedId?: Buffer;
+}
+
+// Encode a fully verified PriceUpdateV2 account
+function encodePriceUpdate(p: MockPrice): Buffer {
+  const data = Buffer.alloc(PRICE_UPDATE_V2_LEN);
+  let offset = 0;
+  PRICE_UPDATE_V2_DISCRIMINATOR.copy(data, offset);
+  offset += 8;
+  offset += 32; // write_authority
+  data.writeUInt8(1, offset); // VerificationLevel::Full
+  offset += 1;
+  p.feedId?.copy(data, offset);
+  offset += 32; // feed_id
+  data.writeBigInt64LE(p.price, offset);
+  offset += 8;
+  data.writeBigUInt64LE(p.conf, offset);
+  offset += 8;
+  data.writeInt32LE(p.expo, offset);
+  offset += 4;
+  const publishTime = BigInt(p.publishTime ?? Math.floor(Date.now() / 1000));
+  data.writeBigInt64LE(publishTime, offset); // publish_time
+  offset += 8;
+  data.writeBigInt64LE(publishTime, offset); // prev_publish_time
+  offset += 8;
+  data.writeBigInt64LE(p.price, offset); // ema_price
+  offset += 8;
+  data.writeBigUInt64LE(p.conf, offset); // ema_conf
+  return data;
+}
+
+// Encode the fields of a Switchboard v2 AggregatorAccountData we read.
+// The mock price is reported with `-expo` decimals; conf is the std deviation.
+function encodeAggregator(p: MockPrice): Buffer {
+  const data = Buffer.alloc(LATEST_ROUND_OFFSET + 65);
+  AGGREGATOR_DISCRIMINATOR.copy(data, 0);
+  data.writeUInt32LE(1, 236); // min_oracle_results
+  data.writeUInt32LE(1, LATEST_ROUND_OFFSET); // num_success
+  const publishTime = BigInt(p.publishTime ?? Math.floor(Date.now() / 1000));
+  data.writeBigInt64LE(publishTime, LATEST_ROUND_OFFSET + 17); // round_open_timestamp
+  data.writeBigInt64LE(p.price, LATEST_ROUND_OFFSET + 25); // result.mantissa (low bits)
+  data.writeBigInt64LE(p.price < 0n ? -1n : 0n, LATEST_ROUND_OFFSET + 33);
+  data.writeUInt32LE(-p.expo, LATEST_ROUND_OFFSET + 41); // result.scale
+  data.writeBigUInt64LE(p.conf, LATEST_ROUND_OFFSET + 45); // std_deviation.mantissa
+  data.writeUInt32LE(-p.expo, LATEST_ROUND_OFFSET + 61); // std_deviation.scale
+  return data;
+}
+
+async function writeMockAccount(
+  provider: anchor.AnchorProvider,
+  owner: PublicKey,
+  account: PublicKey,
+  data: Buffer
+): Promise<void> {
+  const offset = Buffer.alloc(4);
+  offset.writeUInt32LE(0);
+  const ix = new TransactionInstruction({
+    programId: owner,
+    keys: [{ pubkey: account, isSigner: false, isWritable: true }],
+    data: Buffer.concat([offset, data]),
+  });
+  await provider.sendAndConfirm(new Transaction().add(ix));
+}
+
+async function createMockAccount(
+  provider: anchor.AnchorProvider,
+  owner: PublicKey,
+  space: number
+): Promise<PublicKey> {
+  const account = Keypair.generate();
+  const lamports = await provider.connection.getMinimumBalanceForRentExemption(space);
+  const tx = new Transaction().add(
+    SystemProgram.createAccount({
+      fromPubkey: provider.wallet.publicKey,
+      newAccountPubkey: account.publicKey,
+      lamports,
+      space,
+      programId: owner,
+    })
+  );
+  await provider.sendAndConfirm(tx, [account]);
+  return account.publicKey;
+}
+
+// Overwrite the price stored in a mock Pyth feed
+export async function setMockPrice(
+  provider: anchor.AnchorProvider,
+  feed: PublicKey,
+  price: MockPrice
+): Promise<void> {
+  await writeMockAccount(provider, PYTH_RECEIVER_PROGRAM_ID, feed, encodePriceUpdate(price));
+}
+
+// Create a mock Pyth price feed account owned by the mock oracle
+export async function createMockPriceFeed(
+  provider: anchor.AnchorProvider,
+  price: MockPrice
+): Promise<PublicKey> {
+  const feed = await createMockAccount(provider, PYTH_RECEIVER_PROGRAM_ID, PRICE_UPDATE_V2_LEN);
+  await setMockPrice(provider, feed, price);
+  return feed;
+}
+
+// Overwrite the result stored in a mock Switchboard aggregator
+export async function setMockAggregator(
+  provider: anchor.AnchorProvider,
+  aggregator: PublicKey,
+  price: MockPrice
+): Promise<void> {
+  await writeMockAccount(provider, SWITCHBOARD_PROGRAM_ID, aggregator, encodeAggregator(price));
+}
+
+// Create a mock Switchboard aggregator account owned by the mock oracle
+export async function createMockAggregator(
+  provider: anchor.AnchorProvider,
+  price: MockPrice
+): Promise<PublicKey> {
+  const aggregator = await createMockAccount(provider, SWITCHBOARD_PROGRAM_ID, AGGREGATOR_LEN);
+  await setMockAggregator(provider, aggregator, price);
+  return aggregator;
+}
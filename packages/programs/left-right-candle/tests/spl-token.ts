@@ -0,0 +1,175 @@
+import * as anchor from "@coral-xyz/anchor";
+import {
+  Keypair,
+  PublicKey,
+  SystemProgram,
+  Transaction,
+  TransactionInstruction,
+} from "@solana/web3.js";
+
+// Minimal SPL Token / Token-2022 helpers, built by hand to avoid pulling in
+// @solana/spl-token
+export const TOKEN_PROGRAM_ID = new PublicKey("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+export const TOKEN_2022_PROGRAM_ID = new PublicKey("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
+
+const MINT_LEN = 82;
+const ACCOUNT_LEN = 165;
+// Token-2022 accounts with extensions: base account length, account type
+// byte, then a 4-byte TLV header per extension
+const EXTENDED_LEN = ACCOUNT_LEN + 1;
+const TLV_HEADER_LEN = 4;
+const TRANSFER_FEE_CONFIG_LEN = 108;
+const TRANSFER_FEE_AMOUNT_LEN = 8;
+// Token account of a transfer-fee mint (carries a TransferFeeAmount extension)
+export const TRANSFER_FEE_ACCOUNT_LEN = EXTENDED_LEN + TLV_HEADER_LEN + TRANSFER_FEE_AMOUNT_LEN;
+
+// Instruction tags of the token programs
+const MINT_TO = 7;
+const INITIALIZE_ACCOUNT_3 = 18;
+const INITIALIZE_MINT_2 = 20;
+const TRANSFER_FEE_EXTENSION = 26;
+const INITIALIZE_NON_TRANSFERABLE_MINT = 32;
+
+export interface MintOptions {
+  // Token-2022 transfer fee in basis points (uncapped)
+  transferFeeBps?: number;
+  // Token-2022 non-transferable mint
+  nonTransferable?: boolean;
+}
+
+async function createTokenProgramAccount(
+  provider: anchor.AnchorProvider,
+  programId: PublicKey,
+  space: number,
+  init: (account: PublicKey) => TransactionInstruction[]
+): Promise<PublicKey> {
+  const account = Keypair.generate();
+  const lamports = await provider.connection.getMinimumBalanceForRentExemption(space);
+  const tx = new Transaction().add(
+    SystemProgram.createAccount({
+      fromPubkey: provider.wallet.publicKey,
+      newAccountPubkey: account.publicKey,
+      lamports,
+      space,
+      programId,
+    }),
+    ...init(account.publicKey)
+  );
+  await provider.sendAndConfirm(tx, [account]);
+  return account.publicKey;
+}
+
+// Create a mint with the provider wallet as mint authority. Extensions make
+// it a Token-2022 mint.
+export async function createMint(
+  provider: anchor.AnchorProvider,
+  decimals: number,
+  options: MintOptions = {}
+): Promise<PublicKey> {
+  const extensionIxs = (mint: PublicKey): TransactionInstruction[] => {
+    const ixs: TransactionInstruction[] = [];
+    if (options.transferFeeBps !== undefined) {
+      // InitializeTransferFeeConfig: no config or withdraw authority
+      const data = Buffer.alloc(14);
+      data.writeUInt8(TRANSFER_FEE_EXTENSION, 0);
+      data.writeUInt8(0, 1);
+      data.writeUInt16LE(options.transferFeeBps, 4);
+      data.writeBigUInt64LE(0xffffffffffffffffn, 6);
+      ixs.push(extensionIx(mint, data));
+    }
+    if (options.nonTransferable) {
+      ixs.push(extensionIx(mint, Buffer.from([INITIALIZE_NON_TRANSFERABLE_MINT])));
+    }
+    return ixs;
+  };
+
+  const extended = options.transferFeeBps !== undefined || options.nonTransferable;
+  const programId = extended ? TOKEN_2022_PROGRAM_ID : TOKEN_PROGRAM_ID;
+  let space = MINT_LEN;
+  if (extended) {
+    space = EXTENDED_LEN;
+    if (options.transferFeeBps !== undefined) space += TLV_HEADER_LEN + TRANSFER_FEE_CONFIG_LEN;
+    if (options.nonTransferable) space += TLV_HEADER_LEN;
+  }
+
+  return createTokenProgramAccount(provider, programId, space, (mint) => {
+    const data = Buffer.alloc(35);
+    data.writeUInt8(INITIALIZE_MINT_2, 0);
+    data.writeUInt8(decimals, 1);
+    provider.wallet.publicKey.toBuffer().copy(data, 2);
+    // no freeze authority
+    return [
+      ...extensionIxs(mint),
+      new TransactionInstruction({
+        programId,
+        keys: [{ pubkey: mint, isSigner: false, isWritable: true }],
+        data,
+      }),
+    ];
+  });
+}
+
+function extensionIx(mint: PublicKey, data: Buffer): TransactionInstruction {
+  return new TransactionInstruction({
+    programId: TOKEN_2022_PROGRAM_ID,
+    keys: [{ pubkey: mint, isSigner: false, isWritable: true }],
+    data,
+  });
+}
+
+// Create a token account of `mint` owned by `owner`
+export async function createTokenAccount(
+  provider: anchor.AnchorProvider,
+  mint: PublicKey,
+  owner: PublicKey,
+  programId: PublicKey = TOKEN_PROGRAM_ID,
+  space: number = ACCOUNT_LEN
+): Promise<PublicKey> {
+  return createTokenProgramAccount(provider, programId, space, (account) => {
+    const data = Buffer.alloc(33);
+    data.writeUInt8(INITIALIZE_ACCOUNT_3, 0);
+    owner.toBuffer().copy(data, 1);
+    return [
+      new TransactionInstruction({
+        programId,
+        keys: [
+          { pubkey: account, isSigner: false, isWritable: true },
+          { pubkey: mint, isSigner: false, isWritable: false },
+        ],
+        data,
+      }),
+    ];
+  });
+}
+
+// Mint tokens to `account`, signed by the provider wallet
+export async function mintTo(
+  provider: anchor.AnchorProvider,
+  mint: PublicKey,
+  account: PublicKey,
+  amount: bigint,
+  programId: PublicKey = TOKEN_PROGRAM_ID
+): Promise<void> {
+  const data = Buffer.alloc(9);
+  data.writeUInt8(MINT_TO, 0);
+  data.writeBigUInt64LE(amount, 1);
+  const ix = new TransactionInstruction({
+    programId,
+    keys: [
+      { pubkey: mint, isSigner: false, isWritable: true },
+      { pubkey: account, isSigner: false, isWritable: true },
+      { pubkey: provider.wallet.publicKey, isSigner: true, isWritable: false },
+    ],
+    data,
+  });
+  await provider.sendAndConfirm(new Transaction().add(ix));
+}
+
+// Token balance of an account
+export async function tokenBalance(
+  provider: anchor.AnchorProvider,
+  account: PublicKey
+): Promise<bigint> {
+  const balance = await provider.connection.getTokenAccountBalance(account);
+  return BigInt(balance.value.amount);
+}
@@ -0,0 +1,57 @@
+//! Localnet stand-in for oracle programs.
+//!
+//! `left-right-candle` only accepts price accounts owned by the real oracle
+//! programs. For local tests this program is loaded at those addresses via
+//! `[[test.genesis]]` in Anchor.toml, so tests can create accounts owned by
+//! "Pyth" or "Switchboard" and write whatever price layout they need into them.
+//!
+//! Never deploy this program to a public cluster.
+
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+#[cfg(not(feature = "no-entrypoint"))]
+solana_program::entrypoint!(process_instruction);
+
+/// Write raw bytes into a price account owned by this program
+///
+/// Accounts:
+///   0. `[writable]` price account
+///
+/// Instruction data: `offset: u32 (LE) || bytes`
+pub fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let price_account = next_account_info(accounts_iter)?;
+
+    if price_account.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    if !price_account.is_writable {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    if instruction_data.len() < 4 {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    let (offset_bytes, bytes) = instruction_data.split_at(4);
+    let offset = u32::from_le_bytes(offset_bytes.try_into().unwrap()) as usize;
+    let end = offset
+        .checked_add(bytes.len())
+        .ok_or(ProgramError::InvalidInstructionData)?;
+
+    let mut data = price_account.try_borrow_mut_data()?;
+    if end > data.len() {
+        return Err(ProgramError::AccountDataTooSmall);
+    }
+    data[offset..end].copy_from_slice(bytes);
+
+    Ok(())
+}
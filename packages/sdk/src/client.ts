@@ -13,6 +13,7 @@ import {
   getConfigPda,
   getRoundPda,
   getBetPda,
+  getUserStatsPda,
   getVaultPda,
   sideToNumber,
 } from "./utils";
@@ -59,8 +60,8 @@ export class LeftRightClient {
     return null;
   }
 
-  async getBet(roundId: BN, betIndex: number): Promise<Bet | null> {
-    const [betPda] = getBetPda(roundId, betIndex);
+  async getBet(roundId: BN, bettor: PublicKey): Promise<Bet | null> {
+    const [betPda] = getBetPda(roundId, bettor);
     const accountInfo = await this.connection.getAccountInfo(betPda);
     if (!accountInfo) return null;
 
@@ -94,7 +95,8 @@ export class LeftRightClient {
 
     const [configPda] = getConfigPda();
     const [roundPda] = getRoundPda(roundId);
-    const [betPda] = getBetPda(roundId, round.betCount);
+    const [betPda] = getBetPda(roundId, this.wallet.publicKey);
+    const [userStatsPda] = getUserStatsPda(this.wallet.publicKey);
     const [vaultPda] = getVaultPda(roundId);
 
     // Build transaction
@@ -9,6 +9,7 @@ export const SEEDS = {
   ROUND: Buffer.from("round"),
   BET: Buffer.from("bet"),
   VAULT: Buffer.from("vault"),
+  USER_STATS: Buffer.from("user_stats"),
 } as const;
 
 export const SIDE = {
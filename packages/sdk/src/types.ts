@@ -4,7 +4,23 @@ import { BN } from "@coral-xyz/anchor";
 export type Side = "LEFT" | "RIGHT";
 export type SideNumber = 0 | 1;
 
-export type RoundStatus = "Open" | "Locked" | "Settling" | "Settled";
+export type RoundStatus =
+  | "Open"
+  | "Locked"
+  | "Settling"
+  | "Settled"
+  | "Proposed"
+  | "Disputed"
+  | "Refunding"
+  | "Cancelled"
+  | "Claiming";
+
+export interface Price {
+  price: BN;
+  conf: BN;
+  expo: number;
+  publishTime: BN;
+}
 
 export interface Config {
   admin: PublicKey;
@@ -20,15 +36,22 @@ export interface Round {
   roundId: BN;
   assetSymbol: string;
   priceFeed: PublicKey;
-  startPrice: BN;
-  endPrice: BN;
+  priceFeedId: number[];
+  stakeMint: PublicKey | null;
+  startPrice: Price;
+  endPrice: Price;
   startTime: BN;
+  bettingEndTime: BN;
   endTime: BN;
   status: RoundStatus;
   leftPool: BN;
   rightPool: BN;
+  leftWeightedPool: BN;
+  rightWeightedPool: BN;
+  bonusPool: BN;
   betCount: number;
   payoutsProcessed: number;
+  betsClosed: number;
   winningSide: Side | null;
   bump: number;
 }
@@ -38,8 +61,15 @@ export interface Bet {
   bettor: PublicKey;
   side: SideNumber;
   amount: BN;
+  originalAmount: BN;
+  treasuryFee: BN;
+  feeOwed: BN;
+  betTime: BN;
+  weightedAmount: BN;
   betIndex: number;
   paidOut: boolean;
+  withdrawn: boolean;
+  referrer: PublicKey | null;
   bump: number;
 }
 
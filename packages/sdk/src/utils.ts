@@ -14,17 +14,24 @@ export function getRoundPda(roundId: BN): [PublicKey, number] {
   );
 }
 
-export function getBetPda(roundId: BN, betIndex: number): [PublicKey, number] {
+// One position per bettor per round
+export function getBetPda(roundId: BN, bettor: PublicKey): [PublicKey, number] {
   return PublicKey.findProgramAddressSync(
-    [
-      SEEDS.BET,
-      roundId.toArrayLike(Buffer, "le", 8),
-      new BN(betIndex).toArrayLike(Buffer, "le", 4),
-    ],
+    [SEEDS.BET, roundId.toArrayLike(Buffer, "le", 8), bettor.toBuffer()],
     PROGRAM_ID
   );
 }
 
+// Volume stats in SOL, or in `stakeMint` for token rounds
+export function getUserStatsPda(
+  bettor: PublicKey,
+  stakeMint: PublicKey | null = null
+): [PublicKey, number] {
+  const seeds = [SEEDS.USER_STATS, bettor.toBuffer()];
+  if (stakeMint) seeds.push(stakeMint.toBuffer());
+  return PublicKey.findProgramAddressSync(seeds, PROGRAM_ID);
+}
+
 export function getVaultPda(roundId: BN): [PublicKey, number] {
   return PublicKey.findProgramAddressSync(
     [SEEDS.VAULT, roundId.toArrayLike(Buffer, "le", 8)],
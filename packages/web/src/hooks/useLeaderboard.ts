@@ -1,11 +1,8 @@
 import { useQuery } from "@tanstack/react-query";
 import { Connection, PublicKey } from "@solana/web3.js";
-import { BN } from "@coral-xyz/anchor";
-import { Buffer } from "buffer";
+import { getProgramId } from "@/lib/constants";
+import { BetAccount, betFilters, decodeBet, decodeConfig, decodeRound, getRoundPda } from "@/lib/accounts";
 
-const PROGRAM_ID = new PublicKey(
-  process.env.NEXT_PUBLIC_PROGRAM_ID || "81K7nKnv7JiRhBCRNmagKot27Yu82eRWeeNA7dtGGaX6"
-);
 const CONFIG_PDA = new PublicKey(
   process.env.NEXT_PUBLIC_CONFIG_PDA || "DQ6T8gLKAYWhqvxMe8mHRjoY6ZMefRinZu8fkAV4ePA9"
 );
@@ -28,8 +25,9 @@ interface RoundInfo {
   longPool: number;
   shortWeightedPool: number;
   longWeightedPool: number;
+  bonusPool: number;
   betCount: number;
-  status: number;
+  status: string;
 }
 
 interface BetInfo {
@@ -38,7 +36,7 @@ interface BetInfo {
   side: "SHORT" | "LONG";
   amount: number; // pool contribution (after fees)
   originalAmount: number;
-  weight: number;
+  weightedAmount: number;
   paidOut: boolean;
   referrer: string | null;
 }
@@ -50,199 +48,96 @@ export interface ReferralLeaderboardEntry {
   estimatedEarnings: number; // 1% of volume
 }
 
-function getRoundPda(roundId: number): PublicKey {
-  const [pda] = PublicKey.findProgramAddressSync(
-    [Buffer.from("round"), new BN(roundId).toArrayLike(Buffer, "le", 8)],
-    PROGRAM_ID
-  );
-  return pda;
-}
-
-function getBetPda(roundId: number, betIndex: number): PublicKey {
-  const [pda] = PublicKey.findProgramAddressSync(
-    [
-      Buffer.from("bet"),
-      new BN(roundId).toArrayLike(Buffer, "le", 8),
-      new BN(betIndex).toArrayLike(Buffer, "le", 4),
-    ],
-    PROGRAM_ID
-  );
-  return pda;
-}
-
-function decodeRound(data: Buffer): RoundInfo | null {
+function decodeRoundInfo(data: Buffer): RoundInfo | null {
   try {
-    const isNewLayout = data.length > 100;
-    let offset = 8; // skip discriminator
-
-    const roundId = new BN(data.slice(offset, offset + 8), "le").toNumber();
-    offset += 8;
-
-    const assetLen = data.readUInt32LE(offset);
-    offset += 4 + assetLen;
-
-    // start_price, end_price, start_time
-    offset += 8 + 8 + 8;
-
-    if (isNewLayout) {
-      offset += 8; // betting_end_time
-    }
-
-    offset += 8; // end_time
-
-    const status = data[offset];
-    offset += 1;
-
-    const shortPool = new BN(data.slice(offset, offset + 8), "le").toNumber() / 1e9;
-    offset += 8;
-
-    const longPool = new BN(data.slice(offset, offset + 8), "le").toNumber() / 1e9;
-    offset += 8;
-
-    let shortWeightedPool = shortPool;
-    let longWeightedPool = longPool;
-
-    if (isNewLayout) {
-      shortWeightedPool = new BN(data.slice(offset, offset + 8), "le").toNumber() / 1e9;
-      offset += 8;
-      longWeightedPool = new BN(data.slice(offset, offset + 8), "le").toNumber() / 1e9;
-      offset += 8;
-    }
-
-    const betCount = data.readUInt32LE(offset);
-    offset += 4;
-
-    offset += 4; // payouts_processed
-
-    const winningSideDiscriminant = data[offset];
-    offset += 1;
-    let winningSide: "SHORT" | "LONG" | null = null;
-    if (winningSideDiscriminant === 1) {
-      const sideValue = data[offset];
-      winningSide = sideValue === 0 ? "SHORT" : "LONG";
-    }
-
+    const round = decodeRound(data);
     return {
-      roundId,
-      winningSide,
-      shortPool,
-      longPool,
-      shortWeightedPool,
-      longWeightedPool,
-      betCount,
-      status,
+      roundId: round.roundId.toNumber(),
+      winningSide: round.winningSide,
+      shortPool: round.shortPool.toNumber() / 1e9,
+      longPool: round.longPool.toNumber() / 1e9,
+      shortWeightedPool: round.shortWeightedPool.toNumber() / 1e9,
+      longWeightedPool: round.longWeightedPool.toNumber() / 1e9,
+      bonusPool: round.bonusPool.toNumber() / 1e9,
+      betCount: round.betCount,
+      status: round.status,
     };
   } catch {
     return null;
   }
 }
 
-function decodeBet(data: Buffer): BetInfo | null {
-  try {
-    let offset = 8; // skip discriminator
-
-    const roundId = new BN(data.slice(offset, offset + 8), "le").toNumber();
-    offset += 8;
-
-    const bettor = new PublicKey(data.slice(offset, offset + 32)).toBase58();
-    offset += 32;
-
-    const sideByte = data[offset];
-    const side = sideByte === 0 ? "SHORT" : "LONG";
-    offset += 1;
-
-    const amount = new BN(data.slice(offset, offset + 8), "le").toNumber() / 1e9;
-    offset += 8;
-
-    const originalAmount = new BN(data.slice(offset, offset + 8), "le").toNumber() / 1e9;
-    offset += 8;
-
-    offset += 8; // bet_time
-
-    const weight = new BN(data.slice(offset, offset + 8), "le").toNumber();
-    offset += 8;
-
-    offset += 4; // bet_index
-
-    const paidOut = data[offset] === 1;
-    offset += 1;
-
-    // referrer: Option<Pubkey> - 1 byte discriminant + 32 bytes if Some
-    let referrer: string | null = null;
-    const hasReferrer = data[offset] === 1;
-    offset += 1;
-    if (hasReferrer) {
-      referrer = new PublicKey(data.slice(offset, offset + 32)).toBase58();
-    }
-
-    return {
-      roundId,
-      bettor,
-      side,
-      amount,
-      originalAmount,
-      weight,
-      paidOut,
-      referrer,
-    };
-  } catch {
-    return null;
-  }
+function toBetInfo(bet: BetAccount): BetInfo {
+  return {
+    roundId: bet.roundId,
+    bettor: bet.bettor.toBase58(),
+    side: bet.side,
+    amount: bet.amount.toNumber() / 1e9,
+    originalAmount: bet.originalAmount.toNumber() / 1e9,
+    weightedAmount: bet.weightedAmount.toNumber() / 1e9,
+    paidOut: bet.paidOut,
+    referrer: bet.referrer?.toBase58() ?? null,
+  };
 }
 
-async function fetchLeaderboard(): Promise<LeaderboardEntry[]> {
-  const connection = new Connection(RPC_URL, "confirmed");
-
+async function fetchRounds(connection: Connection): Promise<RoundInfo[]> {
   // Get round counter from config
   const configInfo = await connection.getAccountInfo(CONFIG_PDA);
   if (!configInfo) return [];
 
-  const roundCounter = new BN(configInfo.data.slice(92, 100), "le").toNumber();
+  const { roundCounter } = decodeConfig(configInfo.data);
   if (roundCounter === 0) return [];
 
-  // Fetch all rounds
   const roundPdas = Array.from({ length: roundCounter }, (_, i) => getRoundPda(i));
-  const roundAccounts = await connection.getMultipleAccountsInfo(roundPdas);
-
   const rounds: RoundInfo[] = [];
-  for (const account of roundAccounts) {
-    if (account) {
-      const round = decodeRound(account.data as Buffer);
-      if (round && round.status === 3 && round.winningSide) {
-        // Only settled rounds with a winner
-        rounds.push(round);
+
+  // Batch fetch rounds (max 100 at a time)
+  const batchSize = 100;
+  for (let i = 0; i < roundPdas.length; i += batchSize) {
+    const accounts = await connection.getMultipleAccountsInfo(roundPdas.slice(i, i + batchSize));
+    for (const account of accounts) {
+      if (account) {
+        const round = decodeRoundInfo(account.data as Buffer);
+        if (round) {
+          rounds.push(round);
+        }
       }
     }
   }
 
-  if (rounds.length === 0) return [];
-
-  // Fetch all bets from settled rounds
-  const allBetPdas: PublicKey[] = [];
-  const betRoundMap: { roundId: number; betIndex: number }[] = [];
+  return rounds;
+}
 
-  for (const round of rounds) {
-    for (let i = 0; i < round.betCount; i++) {
-      allBetPdas.push(getBetPda(round.roundId, i));
-      betRoundMap.push({ roundId: round.roundId, betIndex: i });
-    }
-  }
+// Bet PDAs are keyed by bettor, so scan for every bet account still open
+async function fetchBets(connection: Connection): Promise<BetInfo[]> {
+  const accounts = await connection.getProgramAccounts(getProgramId(), {
+    filters: betFilters(),
+  });
 
-  // Batch fetch bets (max 100 at a time)
-  const allBets: BetInfo[] = [];
-  const batchSize = 100;
-  for (let i = 0; i < allBetPdas.length; i += batchSize) {
-    const batch = allBetPdas.slice(i, i + batchSize);
-    const accounts = await connection.getMultipleAccountsInfo(batch);
-    for (const account of accounts) {
-      if (account) {
-        const bet = decodeBet(account.data as Buffer);
-        if (bet) {
-          allBets.push(bet);
-        }
+  const bets: BetInfo[] = [];
+  for (const { account } of accounts) {
+    try {
+      const bet = decodeBet(account.data as Buffer);
+      if (!bet.withdrawn) {
+        bets.push(toBetInfo(bet));
       }
+    } catch {
+      // Skip undecodable accounts
     }
   }
+  return bets;
+}
+
+async function fetchLeaderboard(): Promise<LeaderboardEntry[]> {
+  const connection = new Connection(RPC_URL, "confirmed");
+
+  // Only settled rounds with a winner
+  const rounds = (await fetchRounds(connection)).filter(
+    (round) => round.status === "Settled" && round.winningSide
+  );
+  if (rounds.length === 0) return [];
+
+  const allBets = await fetchBets(connection);
 
   // Calculate winnings for each bettor
   const bettorStats: Map<string, { winnings: number; bet: number; wins: number; losses: number }> = new Map();
@@ -259,12 +154,9 @@ async function fetchLeaderboard(): Promise<LeaderboardEntry[]> {
       const losingPool = round.winningSide === "LONG" ? round.shortPool : round.longPool;
       const winningWeightedPool = round.winningSide === "LONG" ? round.longWeightedPool : round.shortWeightedPool;
 
-      // Weighted amount for this bet
-      const weightedAmount = (bet.amount * bet.weight) / 100;
-
-      // Payout = bet back + share of losing pool
-      const share = winningWeightedPool > 0 ? weightedAmount / winningWeightedPool : 0;
-      const bonus = losingPool * share;
+      // Payout = bet back + weighted share of the losing pool and bonus pool
+      const share = winningWeightedPool > 0 ? bet.weightedAmount / winningWeightedPool : 0;
+      const bonus = (losingPool + round.bonusPool) * share;
       const payout = bet.amount + bonus;
 
       stats.winnings += payout;
@@ -311,53 +203,9 @@ export function useLeaderboard() {
 async function fetchReferralLeaderboard(): Promise<ReferralLeaderboardEntry[]> {
   const connection = new Connection(RPC_URL, "confirmed");
 
-  // Get round counter from config
-  const configInfo = await connection.getAccountInfo(CONFIG_PDA);
-  if (!configInfo) return [];
-
-  const roundCounter = new BN(configInfo.data.slice(92, 100), "le").toNumber();
-  if (roundCounter === 0) return [];
-
-  // Fetch all rounds to get bet counts
-  const roundPdas = Array.from({ length: roundCounter }, (_, i) => getRoundPda(i));
-  const roundAccounts = await connection.getMultipleAccountsInfo(roundPdas);
-
-  const rounds: RoundInfo[] = [];
-  for (const account of roundAccounts) {
-    if (account) {
-      const round = decodeRound(account.data as Buffer);
-      if (round) {
-        rounds.push(round);
-      }
-    }
-  }
-
-  if (rounds.length === 0) return [];
-
-  // Fetch all bets from all rounds (not just settled ones - referrals count immediately)
-  const allBetPdas: PublicKey[] = [];
-
-  for (const round of rounds) {
-    for (let i = 0; i < round.betCount; i++) {
-      allBetPdas.push(getBetPda(round.roundId, i));
-    }
-  }
-
-  // Batch fetch bets (max 100 at a time)
-  const allBets: BetInfo[] = [];
-  const batchSize = 100;
-  for (let i = 0; i < allBetPdas.length; i += batchSize) {
-    const batch = allBetPdas.slice(i, i + batchSize);
-    const accounts = await connection.getMultipleAccountsInfo(batch);
-    for (const account of accounts) {
-      if (account) {
-        const bet = decodeBet(account.data as Buffer);
-        if (bet) {
-          allBets.push(bet);
-        }
-      }
-    }
-  }
+  // All bets count (not just settled rounds - referrals count immediately)
+  const allBets = await fetchBets(connection);
+  if (allBets.length === 0) return [];
 
   // Aggregate by referrer
   const referrerStats: Map<string, { count: number; volume: number }> = new Map();
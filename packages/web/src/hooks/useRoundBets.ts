@@ -1,11 +1,8 @@
 import { useQuery } from "@tanstack/react-query";
-import { Connection, PublicKey } from "@solana/web3.js";
-import { BN } from "@coral-xyz/anchor";
-import { Buffer } from "buffer";
+import { Connection } from "@solana/web3.js";
+import { getProgramId } from "@/lib/constants";
+import { betFilters, betWeight, decodeBet } from "@/lib/accounts";
 
-const PROGRAM_ID = new PublicKey(
-  process.env.NEXT_PUBLIC_PROGRAM_ID || "81K7nKnv7JiRhBCRNmagKot27Yu82eRWeeNA7dtGGaX6"
-);
 const RPC_URL = process.env.NEXT_PUBLIC_RPC_URL || "https://api.devnet.solana.com";
 
 export interface BetData {
@@ -15,107 +12,39 @@ export interface BetData {
   amount: number; // in SOL (pool contribution after fees)
   originalAmount: number; // in SOL (original bet amount)
   betTime: number; // unix timestamp
-  weight: number; // weight multiplier (100 = 1x, 150 = 1.5x)
+  weight: number; // average weight multiplier (100 = 1x, 150 = 1.5x)
   betIndex: number;
   paidOut: boolean;
 }
 
-function getBetPda(roundId: number, betIndex: number): PublicKey {
-  const [pda] = PublicKey.findProgramAddressSync(
-    [
-      Buffer.from("bet"),
-      new BN(roundId).toArrayLike(Buffer, "le", 8),
-      new BN(betIndex).toArrayLike(Buffer, "le", 4),
-    ],
-    PROGRAM_ID
-  );
-  return pda;
-}
-
-/**
- * Decode bet account data
- * Layout:
- * - 8 bytes: discriminator
- * - 8 bytes: round_id (u64)
- * - 32 bytes: bettor pubkey
- * - 1 byte: side (enum)
- * - 8 bytes: amount (u64) - pool contribution
- * - 8 bytes: original_amount (u64)
- * - 8 bytes: bet_time (i64)
- * - 8 bytes: weight (u64)
- * - 4 bytes: bet_index (u32)
- * - 1 byte: paid_out (bool)
- * - 1 byte: referrer option discriminant
- * - 32 bytes: referrer pubkey (if Some)
- * - 1 byte: bump
- */
-function decodeBet(data: Buffer): BetData | null {
-  try {
-    let offset = 8; // skip discriminator
-
-    const roundId = new BN(data.slice(offset, offset + 8), "le").toNumber();
-    offset += 8;
-
-    const bettor = new PublicKey(data.slice(offset, offset + 32)).toBase58();
-    offset += 32;
-
-    const sideByte = data[offset];
-    const side = sideByte === 0 ? "SHORT" : "LONG";
-    offset += 1;
-
-    const amount = new BN(data.slice(offset, offset + 8), "le").toNumber() / 1e9; // lamports to SOL
-    offset += 8;
-
-    const originalAmount = new BN(data.slice(offset, offset + 8), "le").toNumber() / 1e9;
-    offset += 8;
-
-    const betTime = new BN(data.slice(offset, offset + 8), "le").toNumber();
-    offset += 8;
-
-    const weight = new BN(data.slice(offset, offset + 8), "le").toNumber();
-    offset += 8;
-
-    const betIndex = data.readUInt32LE(offset);
-    offset += 4;
-
-    const paidOut = data[offset] === 1;
-
-    return {
-      roundId,
-      bettor,
-      side,
-      amount,
-      originalAmount,
-      betTime,
-      weight,
-      betIndex,
-      paidOut,
-    };
-  } catch (err) {
-    console.error("Error decoding bet:", err);
-    return null;
-  }
-}
-
 async function fetchRoundBets(roundId: number, betCount: number): Promise<BetData[]> {
   if (betCount === 0) return [];
 
   const connection = new Connection(RPC_URL, "confirmed");
-  const bets: BetData[] = [];
-
-  // Fetch all bet accounts for this round
-  const betPdas = Array.from({ length: betCount }, (_, i) => getBetPda(roundId, i));
 
-  // Batch fetch accounts
-  const accounts = await connection.getMultipleAccountsInfo(betPdas);
+  // Bet PDAs are keyed by bettor, so find this round's bets by round_id
+  const accounts = await connection.getProgramAccounts(getProgramId(), {
+    filters: betFilters(roundId),
+  });
 
-  for (let i = 0; i < accounts.length; i++) {
-    const account = accounts[i];
-    if (account) {
+  const bets: BetData[] = [];
+  for (const { account } of accounts) {
+    try {
       const bet = decodeBet(account.data as Buffer);
-      if (bet) {
-        bets.push(bet);
-      }
+      if (bet.withdrawn) continue;
+      bets.push({
+        roundId: bet.roundId,
+        bettor: bet.bettor.toBase58(),
+        side: bet.side,
+        amount: bet.amount.toNumber() / 1e9, // lamports to SOL
+        originalAmount: bet.originalAmount.toNumber() / 1e9,
+        betTime: bet.betTime,
+        weight: betWeight(bet),
+        betIndex: bet.betIndex,
+        paidOut: bet.paidOut,
+      });
+    } catch (err) {
+      console.error("Error decoding bet:", err);
     }
   }
 
@@ -0,0 +1,358 @@
+import { PublicKey, SystemProgram, TransactionInstruction } from "@solana/web3.js";
+import { BN, utils } from "@coral-xyz/anchor";
+import { Buffer } from "buffer";
+import { Round, RoundStatus, Side } from "@/types";
+import { getProgramId } from "@/lib/constants";
+
+/**
+ * Hand-written decoders and PDA helpers for the left-right-candle program,
+ * mirroring programs/left-right-candle/src/state. Accounts are Borsh encoded,
+ * so fields after the first variable-length one (asset_symbol) have no fixed
+ * offset and must be read in order.
+ */
+
+// sha256("account:Bet")[0:8]
+const BET_DISCRIMINATOR = Buffer.from([147, 23, 35, 59, 15, 75, 155, 32]);
+// Bet::LEN
+const BET_ACCOUNT_LEN = 137;
+
+// Anchor discriminator for place_bet (sha256("global:place_bet")[0:8])
+const PLACE_BET_DISCRIMINATOR = Buffer.from([222, 62, 67, 220, 63, 166, 126, 33]);
+
+// RoundStatus variants, in program order (Settling is shown as PendingSettlement)
+const ROUND_STATUSES: RoundStatus[] = [
+  "Open",
+  "Locked",
+  "PendingSettlement",
+  "Settled",
+  "Proposed",
+  "Disputed",
+  "Refunding",
+  "Cancelled",
+  "Claiming",
+];
+
+// Number of price sources a round can combine (MAX_PRICE_SOURCES)
+const MAX_PRICE_SOURCES = 3;
+
+// Prices are shown with 8 decimals
+const DISPLAY_EXPO = -8;
+
+export function getConfigPda(): PublicKey {
+  const [pda] = PublicKey.findProgramAddressSync([Buffer.from("config")], getProgramId());
+  return pda;
+}
+
+export function getRoundPda(roundId: number): PublicKey {
+  const [pda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("round"), new BN(roundId).toArrayLike(Buffer, "le", 8)],
+    getProgramId()
+  );
+  return pda;
+}
+
+export function getVaultPda(roundId: number): PublicKey {
+  const [pda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("vault"), new BN(roundId).toArrayLike(Buffer, "le", 8)],
+    getProgramId()
+  );
+  return pda;
+}
+
+// One position per bettor per round
+export function getBetPda(roundId: number, bettor: PublicKey): PublicKey {
+  const [pda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("bet"), new BN(roundId).toArrayLike(Buffer, "le", 8), bettor.toBuffer()],
+    getProgramId()
+  );
+  return pda;
+}
+
+export function getUserStatsPda(bettor: PublicKey): PublicKey {
+  const [pda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("user_stats"), bettor.toBuffer()],
+    getProgramId()
+  );
+  return pda;
+}
+
+// Sequential Borsh reader, starting after the account discriminator
+class Reader {
+  private offset = 8;
+
+  constructor(private data: Buffer) {}
+
+  u8(): number {
+    return this.data.readUInt8(this.offset++);
+  }
+
+  bool(): boolean {
+    return this.u8() === 1;
+  }
+
+  u16(): number {
+    const value = this.data.readUInt16LE(this.offset);
+    this.offset += 2;
+    return value;
+  }
+
+  u32(): number {
+    const value = this.data.readUInt32LE(this.offset);
+    this.offset += 4;
+    return value;
+  }
+
+  i32(): number {
+    const value = this.data.readInt32LE(this.offset);
+    this.offset += 4;
+    return value;
+  }
+
+  u64(): BN {
+    const value = new BN(this.data.subarray(this.offset, this.offset + 8), "le");
+    this.offset += 8;
+    return value;
+  }
+
+  i64(): BN {
+    const value = new BN(this.data.subarray(this.offset, this.offset + 8), "le").fromTwos(64);
+    this.offset += 8;
+    return value;
+  }
+
+  pubkey(): PublicKey {
+    const value = new PublicKey(this.data.subarray(this.offset, this.offset + 32));
+    this.offset += 32;
+    return value;
+  }
+
+  string(): string {
+    const len = this.u32();
+    const value = this.data.subarray(this.offset, this.offset + len).toString("utf8");
+    this.offset += len;
+    return value;
+  }
+
+  option<T>(read: () => T): T | null {
+    return this.bool() ? read() : null;
+  }
+
+  skip(bytes: number): void {
+    this.offset += bytes;
+  }
+}
+
+interface Price {
+  price: BN;
+  expo: number;
+}
+
+function readPrice(reader: Reader): Price {
+  const price = reader.i64();
+  reader.skip(8); // conf
+  const expo = reader.i32();
+  reader.skip(8); // publish_time
+  return { price, expo };
+}
+
+// Price mantissa re-expressed with 8 decimals
+function toDisplayPrice({ price, expo }: Price): BN {
+  const shift = expo - DISPLAY_EXPO;
+  const factor = new BN(10).pow(new BN(Math.abs(shift)));
+  return shift >= 0 ? price.mul(factor) : price.div(factor);
+}
+
+function toSide(side: number): Side {
+  // LEFT (0) = SHORT, RIGHT (1) = LONG
+  return side === 0 ? "SHORT" : "LONG";
+}
+
+export interface ConfigData {
+  roundCounter: number;
+  feeBps: number;
+  minBetLamports: BN;
+  maxBetLamports: BN;
+  treasury: PublicKey;
+}
+
+export function decodeConfig(data: Buffer): ConfigData {
+  const reader = new Reader(data);
+  reader.skip(32); // admin
+  const feeBps = reader.u16();
+  reader.skip(2); // referrer_fee_bps
+  const minBetLamports = reader.u64();
+  const maxBetLamports = reader.u64();
+  const treasury = reader.pubkey();
+  const roundCounter = reader.u64().toNumber();
+  return { roundCounter, feeBps, minBetLamports, maxBetLamports, treasury };
+}
+
+export function decodeRound(data: Buffer): Round {
+  const reader = new Reader(data);
+
+  const roundId = reader.u64();
+  const assetSymbol = reader.string();
+  reader.skip(reader.u32()); // price_sources (one byte each)
+  reader.skip(1); // oracle_quorum
+  const priceFeed = reader.pubkey();
+  reader.skip(32); // price_feed_id
+  reader.skip(32); // switchboard_feed
+  reader.skip(1); // twap_samples
+  reader.skip(1); // tie_policy
+  // weight_curve: Steps(Vec<{ start_bps: u16, weight: u64 }>) or Linear { max_weight: u64 }
+  if (reader.u8() === 0) {
+    reader.skip(reader.u32() * 10);
+  } else {
+    reader.skip(8);
+  }
+  const stakeMint = reader.option(() => reader.pubkey());
+  const startPrice = readPrice(reader);
+  const endPrice = readPrice(reader);
+  for (let i = 0; i < 2 * MAX_PRICE_SOURCES; i++) {
+    reader.option(() => readPrice(reader)); // start_readings, end_readings
+  }
+  const startTime = reader.i64();
+  const bettingEndTime = reader.i64();
+  const endTime = reader.i64();
+  const status = ROUND_STATUSES[reader.u8()] ?? "Open";
+  const shortPool = reader.u64();
+  const longPool = reader.u64();
+  const shortWeightedPool = reader.u64();
+  const longWeightedPool = reader.u64();
+  const bonusPool = reader.u64();
+  const betCount = reader.u32();
+  const payoutsProcessed = reader.u32();
+  reader.skip(4); // bets_closed
+  const winningSide = reader.option(() => toSide(reader.u8()));
+
+  return {
+    roundId,
+    assetSymbol,
+    priceFeed,
+    startPrice: toDisplayPrice(startPrice),
+    endPrice: toDisplayPrice(endPrice),
+    startTime,
+    bettingEndTime,
+    endTime,
+    status,
+    shortPool,
+    longPool,
+    shortWeightedPool,
+    longWeightedPool,
+    bonusPool,
+    stakeMint,
+    betCount,
+    payoutsProcessed,
+    winningSide,
+  };
+}
+
+export interface BetAccount {
+  roundId: number;
+  bettor: PublicKey;
+  side: Side;
+  amount: BN; // pool contribution after fees
+  originalAmount: BN;
+  treasuryFee: BN;
+  feeOwed: BN; // treasury fee refund not yet paid back
+  betTime: number;
+  weightedAmount: BN; // amount times its timing weight
+  betIndex: number;
+  paidOut: boolean;
+  withdrawn: boolean;
+  referrer: PublicKey | null;
+}
+
+export function decodeBet(data: Buffer): BetAccount {
+  const reader = new Reader(data);
+  return {
+    roundId: reader.u64().toNumber(),
+    bettor: reader.pubkey(),
+    side: toSide(reader.u8()),
+    amount: reader.u64(),
+    originalAmount: reader.u64(),
+    treasuryFee: reader.u64(),
+    feeOwed: reader.u64(),
+    betTime: reader.i64().toNumber(),
+    weightedAmount: reader.u64(),
+    betIndex: reader.u32(),
+    paidOut: reader.bool(),
+    withdrawn: reader.bool(),
+    referrer: reader.option(() => reader.pubkey()),
+  };
+}
+
+// Average timing weight of a position (100 = 1x, 150 = 1.5x)
+export function betWeight(bet: BetAccount): number {
+  if (bet.amount.isZero()) return 100;
+  return bet.weightedAmount.muln(100).div(bet.amount).toNumber();
+}
+
+/**
+ * getProgramAccounts filters matching bet accounts, optionally of one round.
+ * Bet addresses are keyed by bettor, so a round's bets are found by scanning
+ * rather than by index.
+ */
+export function betFilters(roundId?: number) {
+  const filters = [
+    { dataSize: BET_ACCOUNT_LEN },
+    { memcmp: { offset: 0, bytes: utils.bytes.bs58.encode(BET_DISCRIMINATOR) } },
+  ];
+  if (roundId !== undefined) {
+    const roundIdBytes = new BN(roundId).toArrayLike(Buffer, "le", 8);
+    filters.push({ memcmp: { offset: 8, bytes: utils.bytes.bs58.encode(roundIdBytes) } });
+  }
+  return filters;
+}
+
+/**
+ * place_bet for a SOL round. Token accounts are left out (the program id
+ * stands in for a missing optional account).
+ */
+export function placeBetInstruction(params: {
+  roundId: number;
+  bettor: PublicKey;
+  treasury: PublicKey;
+  side: number;
+  lamports: BN;
+  referrer?: PublicKey | null;
+  minMultiplierBps?: BN | null;
+}): TransactionInstruction {
+  const { roundId, bettor, treasury, side, lamports, referrer, minMultiplierBps } = params;
+  const programId = getProgramId();
+  const none = { pubkey: programId, isSigner: false, isWritable: false };
+
+  // Discriminator + side (u8) + amount_lamports (u64) + min_multiplier_bps (Option<u64>)
+  const data = Buffer.concat([
+    PLACE_BET_DISCRIMINATOR,
+    Buffer.from([side]),
+    lamports.toArrayLike(Buffer, "le", 8),
+    minMultiplierBps
+      ? Buffer.concat([Buffer.from([1]), minMultiplierBps.toArrayLike(Buffer, "le", 8)])
+      : Buffer.from([0]),
+  ]);
+
+  return new TransactionInstruction({
+    programId,
+    keys: [
+      { pubkey: getConfigPda(), isSigner: false, isWritable: false },
+      { pubkey: getRoundPda(roundId), isSigner: false, isWritable: true },
+      { pubkey: getBetPda(roundId, bettor), isSigner: false, isWritable: true },
+      { pubkey: getUserStatsPda(bettor), isSigner: false, isWritable: true },
+      none, // mint_user_stats
+      { pubkey: getVaultPda(roundId), isSigner: false, isWritable: true },
+      { pubkey: treasury, isSigner: false, isWritable: true },
+      { pubkey: bettor, isSigner: true, isWritable: true },
+      referrer ? { pubkey: referrer, isSigner: false, isWritable: true } : none,
+      none, // mint_config
+      none, // stake_mint
+      none, // vault_token
+      none, // bettor_token
+      none, // treasury_token
+      none, // referrer_token
+      none, // token_program
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+    ],
+    data,
+  });
+}
@@ -9,22 +9,22 @@ import {
   Connection,
   PublicKey,
   Transaction,
-  SystemProgram,
   LAMPORTS_PER_SOL,
 } from "@solana/web3.js";
 import { BN } from "@coral-xyz/anchor";
 import type { NextApiRequest, NextApiResponse } from "next";
 import tokensData from "@/data/tokens.json";
+import {
+  decodeConfig,
+  decodeRound,
+  getConfigPda,
+  getRoundPda,
+  placeBetInstruction,
+} from "@/lib/accounts";
 
 // Program constants
-const PROGRAM_ID = new PublicKey(
-  process.env.NEXT_PUBLIC_PROGRAM_ID || "81K7nKnv7JiRhBCRNmagKot27Yu82eRWeeNA7dtGGaX6"
-);
 const RPC_URL = process.env.NEXT_PUBLIC_RPC_URL || "https://api.devnet.solana.com";
 
-// Anchor discriminator for place_bet (sha256("global:place_bet")[0:8])
-const PLACE_BET_DISCRIMINATOR = Buffer.from([222, 62, 67, 220, 63, 166, 126, 33]);
-
 // Token metadata for display - built from tokens.json
 interface TokenInfo {
   symbol: string;
@@ -57,43 +57,6 @@ interface RoundData {
   status: string;
 }
 
-// Helper to derive PDAs - defined early so other functions can use them
-function getConfigPda(): PublicKey {
-  const [pda] = PublicKey.findProgramAddressSync(
-    [Buffer.from("config")],
-    PROGRAM_ID
-  );
-  return pda;
-}
-
-function getRoundPda(roundId: number): PublicKey {
-  const [pda] = PublicKey.findProgramAddressSync(
-    [Buffer.from("round"), new BN(roundId).toArrayLike(Buffer, "le", 8)],
-    PROGRAM_ID
-  );
-  return pda;
-}
-
-function getBetPda(roundId: number, betIndex: number): PublicKey {
-  const [pda] = PublicKey.findProgramAddressSync(
-    [
-      Buffer.from("bet"),
-      new BN(roundId).toArrayLike(Buffer, "le", 8),
-      new BN(betIndex).toArrayLike(Buffer, "le", 4),
-    ],
-    PROGRAM_ID
-  );
-  return pda;
-}
-
-function getVaultPda(roundId: number): PublicKey {
-  const [pda] = PublicKey.findProgramAddressSync(
-    [Buffer.from("vault"), new BN(roundId).toArrayLike(Buffer, "le", 8)],
-    PROGRAM_ID
-  );
-  return pda;
-}
-
 async function fetchTokenPrice(coingeckoId: string): Promise<{ price: number; change24h: number } | null> {
   try {
     const res = await fetch(
@@ -115,7 +78,6 @@ async function fetchTokenPrice(coingeckoId: string): Promise<{ price: number; ch
 async function getRoundData(roundId: number): Promise<RoundData | null> {
   console.log("getRoundData called with roundId:", roundId);
   console.log("RPC_URL:", RPC_URL);
-  console.log("PROGRAM_ID:", PROGRAM_ID.toBase58());
 
   try {
     const connection = new Connection(RPC_URL, "confirmed");
@@ -132,53 +94,14 @@ async function getRoundData(roundId: number): Promise<RoundData | null> {
 
     console.log("Round account data length:", roundAccount.data.length);
 
-    // Parse round account data (Anchor account structure)
-    const data = roundAccount.data;
-    let offset = 8; // Skip discriminator
-
-    // round_id: u64
-    offset += 8;
-
-    // asset_symbol: String (borsh: 4-byte length + bytes)
-    const assetLen = data.readUInt32LE(offset);
-    offset += 4;
-    const assetSymbol = data.slice(offset, offset + assetLen).toString("utf-8");
-    offset += assetLen;
-
-    // start_price: i64
-    const startPrice = Number(data.readBigInt64LE(offset)) / 1e8;
-    offset += 8;
-
-    // end_price: i64
-    offset += 8; // skip end_price
-
-    // start_time: i64
-    offset += 8; // skip start_time
-
-    // betting_end_time: i64 - always present
-    const bettingEndTime = Number(data.readBigInt64LE(offset));
-    offset += 8;
-
-    // end_time: i64
-    const endTime = Number(data.readBigInt64LE(offset));
-    offset += 8;
-
-    // status: enum (1 byte)
-    const status = data.readUInt8(offset);
-    offset += 1;
-    const statusStr = status === 0 ? "Open" : status === 1 ? "Locked" : status === 2 ? "Settling" : "Settled";
-
-    // left_pool: u64 (lamports)
-    const leftPool = Number(data.readBigUInt64LE(offset)) / LAMPORTS_PER_SOL;
-    offset += 8;
-
-    // right_pool: u64 (lamports)
-    const rightPool = Number(data.readBigUInt64LE(offset)) / LAMPORTS_PER_SOL;
-    offset += 8;
-
-    // Skip weighted pools - always present
-    offset += 8; // left_weighted_pool
-    offset += 8; // right_weighted_pool
+    const round = decodeRound(roundAccount.data);
+    const assetSymbol = round.assetSymbol;
+    const startPrice = round.startPrice.toNumber() / 1e8;
+    const bettingEndTime = round.bettingEndTime.toNumber();
+    const endTime = round.endTime.toNumber();
+    const statusStr = round.status === "PendingSettlement" ? "Settling" : round.status;
+    const leftPool = round.shortPool.toNumber() / LAMPORTS_PER_SOL;
+    const rightPool = round.longPool.toNumber() / LAMPORTS_PER_SOL;
 
     // Get token info
     const token = TOKENS[assetSymbol] || TOKENS.WIF;
@@ -316,9 +239,7 @@ export default async function handler(
       if (!configAccount) {
         return res.status(500).json({ error: "Config not initialized" });
       }
-      // Config layout: 8 (disc) + 32 (admin) + 2 (fee_bps) + 2 (ref_fee) + 8 (min) + 8 (max) + 32 (treasury) + 8 (round_counter)
-      // round_counter is at offset 92
-      const roundCounter = Number(configAccount.data.readBigUInt64LE(92));
+      const { roundCounter } = decodeConfig(configAccount.data);
       // Current active round is roundCounter - 1 (since counter increments after starting a round)
       roundId = Math.max(0, roundCounter - 1);
     } catch (error) {
@@ -406,7 +327,7 @@ export default async function handler(
       // Connect to Solana
       const connection = new Connection(RPC_URL, "confirmed");
 
-      // Get round data to find bet count
+      // Get the round (to check it exists) and config (for the treasury)
       const roundPda = getRoundPda(roundId);
       const configPda = getConfigPda();
 
@@ -422,65 +343,33 @@ export default async function handler(
         return res.status(500).json({ error: "Config not initialized" });
       }
 
-      // Parse bet_count from round account
-      // Structure: discriminator(8) + round_id(8) + asset_symbol(4+len) + start_price(8) + end_price(8) + start_time(8) + end_time(8) + status(1) + left_pool(8) + right_pool(8) + bet_count(4)
-      const roundData = roundAccount.data;
-      let offset = 8 + 8; // skip discriminator and round_id
-      const assetLen = roundData.readUInt32LE(offset);
-      offset += 4 + assetLen + 8 + 8 + 8 + 8 + 1 + 8 + 8; // skip to bet_count
-      const betCount = roundData.readUInt32LE(offset);
-
-      // Parse treasury from config
-      // Structure: discriminator(8) + admin(32) + fee_bps(2) + referrer_fee_bps(2) + min_bet(8) + max_bet(8) + treasury(32)
-      const treasuryOffset = 8 + 32 + 2 + 2 + 8 + 8;
-      const treasury = new PublicKey(configAccount.data.slice(treasuryOffset, treasuryOffset + 32));
-
-      // Build the place_bet instruction
-      const betPda = getBetPda(roundId, betCount);
-      const vaultPda = getVaultPda(roundId);
-
-      // Create instruction data for place_bet
-      // Discriminator (8 bytes) + side (1 byte) + amount (8 bytes)
-      const instructionData = Buffer.concat([
-        PLACE_BET_DISCRIMINATOR,
-        Buffer.from([sideNum]),
-        new BN(lamports).toArrayLike(Buffer, "le", 8),
-      ]);
+      const { treasury } = decodeConfig(configAccount.data);
 
-      // Build accounts array matching place_bet instruction order:
-      // config, round, bet, vault, treasury, bettor, referrer (optional), system_program
-      const accounts = [
-        { pubkey: configPda, isSigner: false, isWritable: false },
-        { pubkey: roundPda, isSigner: false, isWritable: true },
-        { pubkey: betPda, isSigner: false, isWritable: true },
-        { pubkey: vaultPda, isSigner: false, isWritable: true },
-        { pubkey: treasury, isSigner: false, isWritable: true },
-        { pubkey: userPubkey, isSigner: true, isWritable: true },
-      ];
-
-      // Add referrer if provided (must come before system_program)
+      // Referrer, unless it is missing, invalid or a self-referral
+      let referrer: PublicKey | null = null;
       if (ref) {
         try {
           const referrerPubkey = new PublicKey(ref as string);
-          // Only add if not self-referral
           if (!referrerPubkey.equals(userPubkey)) {
-            accounts.push({ pubkey: referrerPubkey, isSigner: false, isWritable: true });
+            referrer = referrerPubkey;
           }
         } catch {
           // Invalid referrer pubkey, skip it
         }
       }
 
-      // System program last
-      accounts.push({ pubkey: SystemProgram.programId, isSigner: false, isWritable: false });
-
       // Create transaction
       const transaction = new Transaction();
-      transaction.add({
-        programId: PROGRAM_ID,
-        keys: accounts,
-        data: instructionData,
-      });
+      transaction.add(
+        placeBetInstruction({
+          roundId,
+          bettor: userPubkey,
+          treasury,
+          side: sideNum,
+          lamports: new BN(lamports),
+          referrer,
+        })
+      );
 
       // Get recent blockhash
       const { blockhash, lastValidBlockHeight } = await connection.getLatestBlockhash();
@@ -14,8 +14,8 @@ export interface Round {
   roundId: BN;
   assetSymbol: string;
   priceFeed: PublicKey;
-  startPrice: BN;
-  endPrice: BN;
+  startPrice: BN; // 8 decimals
+  endPrice: BN; // 8 decimals
   startTime: BN;
   bettingEndTime: BN;
   endTime: BN;
@@ -24,12 +24,23 @@ export interface Round {
   longPool: BN;
   shortWeightedPool: BN;
   longWeightedPool: BN;
+  bonusPool: BN;
+  stakeMint: PublicKey | null;
   betCount: number;
   payoutsProcessed: number;
   winningSide: Side | null;
 }
 
-export type RoundStatus = "Open" | "Locked" | "PendingSettlement" | "Settled" | "Cancelled";
+export type RoundStatus =
+  | "Open"
+  | "Locked"
+  | "PendingSettlement"
+  | "Settled"
+  | "Proposed"
+  | "Disputed"
+  | "Refunding"
+  | "Cancelled"
+  | "Claiming";
 
 export interface Bet {
   roundId: BN;
@@ -37,10 +48,14 @@ export interface Bet {
   side: number;
   amount: BN;
   originalAmount: BN;
+  treasuryFee: BN;
+  feeOwed: BN;
   betTime: BN;
-  weight: BN;
+  weightedAmount: BN;
   betIndex: number;
   paidOut: boolean;
+  withdrawn: boolean;
+  referrer: PublicKey | null;
 }
 
 export interface UserBet extends Bet {